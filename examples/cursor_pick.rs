@@ -0,0 +1,92 @@
+use bevy::{color::palettes::tailwind, prelude::*, window::PrimaryWindow};
+use bevy_fixed_viewport::{FixedViewport, FixedViewportPlugin, FixedViewports, LetterboxColor};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FixedViewportPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, (follow_cursor, print_clicks))
+        .run();
+}
+
+/// Marks the sprite following the cursor.
+#[derive(Component)]
+struct CursorMarker;
+
+fn startup(mut commands: Commands) {
+    commands.spawn((
+        Camera2dBundle::default(),
+        FixedViewport::new(16. / 9.),
+        LetterboxColor(Color::BLACK),
+    ));
+
+    // create a rectangle that will always fill the screen
+    commands.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: Color::Srgba(tailwind::RED_500),
+            custom_size: Some(Vec2::new(10000., 10000.)),
+            ..default()
+        },
+        ..default()
+    });
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::WHITE,
+                custom_size: Some(Vec2::splat(16.)),
+                ..default()
+            },
+            transform: Transform::from_xyz(0., 0., 1.),
+            ..default()
+        },
+        CursorMarker,
+    ));
+}
+
+/// The world position under the cursor, or `None` when it's over a letterbox bar or outside the window.
+fn cursor_world_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<Entity, With<FixedViewport>>,
+    fixed_viewports: &FixedViewports,
+) -> Option<Vec2> {
+    let cursor = window_query.get_single().ok()?.cursor_position()?;
+    let camera = camera_query.get_single().ok()?;
+    fixed_viewports.cursor_to_world(camera, cursor)
+}
+
+fn follow_cursor(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<Entity, With<FixedViewport>>,
+    fixed_viewports: FixedViewports,
+    mut marker_query: Query<(&mut Transform, &mut Visibility), With<CursorMarker>>,
+) {
+    let (mut transform, mut visibility) = marker_query.single_mut();
+
+    // hide the marker while the cursor is over the bars
+    match cursor_world_position(&window_query, &camera_query, &fixed_viewports) {
+        Some(position) => {
+            transform.translation = position.extend(transform.translation.z);
+            *visibility = Visibility::Inherited;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
+
+fn print_clicks(
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<Entity, With<FixedViewport>>,
+    fixed_viewports: FixedViewports,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    // clicks on the bars are ignored
+    match cursor_world_position(&window_query, &camera_query, &fixed_viewports) {
+        Some(position) => println!("clicked the world at {position}"),
+        None => println!("clicked a letterbox bar, ignoring"),
+    }
+}
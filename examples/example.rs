@@ -3,14 +3,14 @@ use bevy::{
     input::{keyboard::KeyboardInput, ButtonState},
     prelude::*,
 };
-use bevy_fixed_viewport::{FixedViewport, FixedViewportPlugin};
+use bevy_fixed_viewport::{AspectRatio, FixedViewport, FixedViewportPlugin, ScaleMode};
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(FixedViewportPlugin)
+        .add_plugins(FixedViewportPlugin::default())
         .add_systems(Startup, startup)
-        .add_systems(Update, change_aspect_ratio)
+        .add_systems(Update, (change_aspect_ratio, change_scale_mode))
         .run();
 }
 
@@ -18,7 +18,8 @@ fn startup(mut commands: Commands) {
     commands.spawn((
         Camera2dBundle::default(),
         FixedViewport {
-            aspect_ratio: 16. / 9.,
+            aspect_ratio: AspectRatio::Value(16. / 9.),
+            ..default()
         },
     ));
 
@@ -48,10 +49,34 @@ fn change_aspect_ratio(
         } {
             Some(change) => {
                 for mut fixed_viewport in camera_query.iter_mut() {
-                    fixed_viewport.aspect_ratio += change;
+                    fixed_viewport.aspect_ratio =
+                        AspectRatio::Value(fixed_viewport.fixed_aspect_ratio() + change);
                 }
             }
             None => continue,
         }
     }
 }
+
+fn change_scale_mode(
+    mut input_events: EventReader<KeyboardInput>,
+    mut camera_query: Query<&mut FixedViewport>,
+) {
+    for event in input_events.read() {
+        if event.state != ButtonState::Pressed || event.key_code != KeyCode::KeyM {
+            continue;
+        }
+
+        // changing the mode alone is enough for the viewport to be refitted
+        for mut fixed_viewport in camera_query.iter_mut() {
+            fixed_viewport.mode = match fixed_viewport.mode {
+                ScaleMode::Fit => ScaleMode::Fill,
+                ScaleMode::Fill => ScaleMode::Stretch,
+                ScaleMode::Stretch => ScaleMode::Integer {
+                    base_resolution: UVec2::new(320, 180),
+                },
+                _ => ScaleMode::Fit,
+            };
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use bevy_fixed_viewport::{
+    CurrentViewport, FixedViewport, FixedViewportPlugin, TargetSizeOverride,
+};
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default());
+
+    // without a window to render to, the camera is fitted to the overridden size instead
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera::default(),
+            FixedViewport::new(16. / 9.),
+            TargetSizeOverride(UVec2::new(1920, 1200)),
+        ))
+        .id();
+    app.update();
+
+    let current_viewport = app.world().get::<CurrentViewport>(camera).unwrap();
+    println!(
+        "fitted a viewport of {} at {}",
+        current_viewport.0.physical_size, current_viewport.0.physical_position
+    );
+}
@@ -0,0 +1,49 @@
+use bevy::{color::palettes::tailwind, prelude::*};
+use bevy_fixed_viewport::{AspectRatio, FixedViewport, FixedViewportPlugin, ViewportAlignment};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(FixedViewportPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(mut commands: Commands) {
+    // the main camera fits a 16:9 viewport to the whole window
+    commands.spawn((
+        Camera2dBundle::default(),
+        FixedViewport {
+            aspect_ratio: AspectRatio::Value(16. / 9.),
+            ..default()
+        },
+    ));
+
+    // the minimap fits a square viewport to the top-right corner, drawn over the main camera
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                clear_color: ClearColorConfig::Custom(Color::Srgba(tailwind::BLUE_900)),
+                ..default()
+            },
+            ..default()
+        },
+        FixedViewport {
+            aspect_ratio: AspectRatio::Value(1.),
+            alignment: ViewportAlignment::TopRight,
+            region: Rect::new(0.75, 0., 1., 0.25),
+            ..default()
+        },
+    ));
+
+    // create a rectangle that will always fill the screen
+    commands.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: Color::Srgba(tailwind::RED_500),
+            custom_size: Some(Vec2::new(10000., 10000.)),
+            ..default()
+        },
+        ..default()
+    });
+}
@@ -0,0 +1,81 @@
+use bevy::prelude::*;
+
+use crate::{AspectRatio, FixedViewport};
+
+/// Common aspect ratios a fixed viewport can be set to, such as for cycling through in a settings screen.
+#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FixedAspect {
+    #[default]
+    R16x9,
+    R16x10,
+    R4x3,
+    R21x9,
+    /// Any other aspect ratio, which isn't part of the cycle.
+    Custom(f32),
+}
+
+impl FixedAspect {
+    /// The presets in the order they're cycled through.
+    pub const PRESETS: [Self; 4] = [Self::R16x9, Self::R16x10, Self::R4x3, Self::R21x9];
+
+    /// The aspect ratio, exact for a preset.
+    pub fn aspect_ratio(&self) -> AspectRatio {
+        match self {
+            FixedAspect::R16x9 => AspectRatio::Ratio(16, 9),
+            FixedAspect::R16x10 => AspectRatio::Ratio(16, 10),
+            FixedAspect::R4x3 => AspectRatio::Ratio(4, 3),
+            FixedAspect::R21x9 => AspectRatio::Ratio(21, 9),
+            FixedAspect::Custom(aspect_ratio) => AspectRatio::Value(*aspect_ratio),
+        }
+    }
+
+    /// The aspect ratio as a float.
+    pub fn ratio(&self) -> f32 {
+        self.aspect_ratio().value()
+    }
+
+    /// The next preset in the cycle, wrapping around, where a custom aspect ratio starts the cycle again.
+    pub fn next(&self) -> Self {
+        match Self::PRESETS.iter().position(|preset| preset == self) {
+            Some(index) => Self::PRESETS[(index + 1) % Self::PRESETS.len()],
+            None => Self::PRESETS[0],
+        }
+    }
+
+    /// The previous preset in the cycle, wrapping around, where a custom aspect ratio ends the cycle again.
+    pub fn prev(&self) -> Self {
+        let len = Self::PRESETS.len();
+        match Self::PRESETS.iter().position(|preset| preset == self) {
+            Some(index) => Self::PRESETS[(index + len - 1) % len],
+            None => Self::PRESETS[len - 1],
+        }
+    }
+}
+
+impl FixedViewport {
+    /// A fixed viewport with the given preset or custom aspect ratio.
+    pub fn from_aspect(aspect: FixedAspect) -> Self {
+        Self::default().with_aspect(aspect)
+    }
+
+    /// Sets the aspect ratio to the given preset or custom aspect ratio.
+    pub fn with_aspect(mut self, aspect: FixedAspect) -> Self {
+        self.set_aspect(aspect);
+        self
+    }
+
+    /// Sets the aspect ratio to the given preset or custom aspect ratio, storing presets exactly.
+    pub fn set_aspect(&mut self, aspect: FixedAspect) {
+        self.aspect_ratio = aspect.aspect_ratio();
+    }
+
+    /// The preset matching the aspect ratio however it's written, such as `1920:1080` for 16:9,
+    /// or a custom aspect ratio otherwise.
+    pub fn aspect(&self) -> FixedAspect {
+        FixedAspect::PRESETS
+            .into_iter()
+            .find(|preset| preset.aspect_ratio().matches(&self.aspect_ratio))
+            .unwrap_or(FixedAspect::Custom(self.fixed_aspect_ratio()))
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+
+use crate::{overlay::ManagedBy, same_target};
+
+/// Attach this to a camera with a fixed viewport to clear its whole render target to a color before it draws.
+///
+/// Unlike [`LetterboxColor`](crate::LetterboxColor), nothing is drawn over the bars, they're just cleared
+/// by a managed camera ordered below every camera on the target, so they never show stale contents of the render target.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct BarClearColor(pub Color);
+
+/// Tracks the managed camera that clears a camera's render target.
+#[derive(Component)]
+pub(crate) struct BarClear {
+    camera: Entity,
+}
+
+/// Spawns the managed camera for cameras that have just been given a bar clear color.
+pub(crate) fn spawn_bar_clear_cameras(
+    camera_query: Query<Entity, (With<BarClearColor>, Without<BarClear>)>,
+    mut commands: Commands,
+) {
+    for entity in camera_query.iter() {
+        // the camera renders nothing, it only clears the whole target
+        let camera = commands
+            .spawn((
+                Camera2dBundle::default(),
+                RenderLayers::none(),
+                ManagedBy::<BarClearColor>::new(entity),
+            ))
+            .id();
+        commands.entity(entity).insert(BarClear { camera });
+    }
+}
+
+/// Keeps each managed camera clearing its owner's whole render target before anything else on the target draws,
+/// ordered below every other camera on the target so its order never ties with another.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_bar_clear_cameras(
+    owner_query: Query<
+        (Entity, &Camera, &BarClearColor, &BarClear),
+        Without<ManagedBy<BarClearColor>>,
+    >,
+    other_query: Query<&Camera, Without<ManagedBy<BarClearColor>>>,
+    mut camera_query: Query<&mut Camera, With<ManagedBy<BarClearColor>>>,
+) {
+    // stack the clears of cameras sharing a target in the order of their owners
+    let mut owners: Vec<_> = owner_query.iter().collect();
+    owners.sort_by_key(|(entity, owner_camera, ..)| (owner_camera.order, *entity));
+
+    for (index, (_, owner_camera, bar_clear_color, bar_clear)) in owners.iter().enumerate() {
+        let target = &owner_camera.target;
+        let bottom = other_query
+            .iter()
+            .filter(|camera| same_target(&camera.target, target))
+            .map(|camera| camera.order)
+            .min()
+            .unwrap_or(owner_camera.order);
+        let above = owners[..index]
+            .iter()
+            .filter(|(_, other, ..)| same_target(&other.target, target))
+            .count();
+        let order = bottom.saturating_sub(1).saturating_sub(above as isize);
+
+        let mut camera = match camera_query.get_mut(bar_clear.camera) {
+            Ok(camera) => camera,
+            Err(_) => continue,
+        };
+        // only touch what differs, so the clear camera's change detection stays meaningful
+        if !same_target(&camera.target, target) {
+            camera.target = target.clone();
+        }
+        if camera.order != order {
+            camera.order = order;
+        }
+        if camera.is_active != owner_camera.is_active {
+            camera.is_active = owner_camera.is_active;
+        }
+        let clear_color = ClearColorConfig::Custom(bar_clear_color.0);
+        if !matches!(camera.clear_color, ClearColorConfig::Custom(color) if color == bar_clear_color.0)
+        {
+            camera.clear_color = clear_color;
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::{
+    prelude::*,
+    render::camera::{CameraProjection, ScalingMode},
+};
+
+use crate::same_scaling_mode;
+
+/// The part of a fitted image shown when nothing is cropped, as fractions of the image from its top-left corner.
+pub(crate) const FULL_CROP: Rect = Rect {
+    min: Vec2::ZERO,
+    max: Vec2::ONE,
+};
+
+/// The scaling mode and origin a camera's projection had before it was cropped, restored once it isn't.
+#[derive(Component)]
+pub(crate) struct UncroppedProjection {
+    scaling_mode: ScalingMode,
+    viewport_origin: Vec2,
+}
+
+impl UncroppedProjection {
+    /// Puts the projection back the way it was before it was cropped.
+    pub(crate) fn restore(&self, projection: &mut Mut<OrthographicProjection>) {
+        if !same_scaling_mode(&projection.scaling_mode, &self.scaling_mode) {
+            projection.scaling_mode = self.scaling_mode;
+        }
+        if projection.viewport_origin != self.viewport_origin {
+            projection.viewport_origin = self.viewport_origin;
+        }
+    }
+}
+
+/// Keeps a camera's projection showing only the visible part of its fitted image, given as the crop,
+/// so the content is scaled as if the whole image were drawn and the overflow is cut off.
+///
+/// The projection is given the locked scaling mode if any, and is put back the way it was once uncropped.
+pub(crate) fn crop_projection(
+    entity: Entity,
+    projection: &mut Mut<OrthographicProjection>,
+    uncropped: Option<&UncroppedProjection>,
+    locked: Option<ScalingMode>,
+    crop: Rect,
+    viewport_size: Vec2,
+    commands: &mut Commands,
+) {
+    // the projection as it would be without the crop
+    let scaling_mode = locked
+        .or(uncropped.map(|uncropped| uncropped.scaling_mode))
+        .unwrap_or(projection.scaling_mode);
+    let viewport_origin = uncropped.map_or(projection.viewport_origin, |uncropped| {
+        uncropped.viewport_origin
+    });
+
+    let cropped = crop != FULL_CROP
+        && crop.width() > 0.
+        && crop.height() > 0.
+        && viewport_size.cmpgt(Vec2::ZERO).all()
+        && projection.scale != 0.;
+    let (scaling_mode, viewport_origin) = match cropped {
+        true => {
+            // the world area of the whole fitted image, of which only the crop is shown
+            let mut whole = OrthographicProjection {
+                scaling_mode,
+                viewport_origin,
+                ..projection.clone()
+            };
+            let whole_size = viewport_size / crop.size();
+            whole.update(whole_size.x, whole_size.y);
+            let area = whole.area;
+            let visible = Rect::new(
+                area.min.x + crop.min.x * area.width(),
+                area.max.y - crop.max.y * area.height(),
+                area.min.x + crop.max.x * area.width(),
+                area.max.y - crop.min.y * area.height(),
+            );
+
+            if uncropped.is_none() {
+                commands.entity(entity).insert(UncroppedProjection {
+                    scaling_mode,
+                    viewport_origin,
+                });
+            }
+            (
+                ScalingMode::Fixed {
+                    width: visible.width() / projection.scale,
+                    height: visible.height() / projection.scale,
+                },
+                -visible.min / visible.size(),
+            )
+        }
+        false => {
+            if uncropped.is_some() {
+                commands.entity(entity).remove::<UncroppedProjection>();
+            }
+            (scaling_mode, viewport_origin)
+        }
+    };
+
+    // only assign when different, as a changed projection makes bevy recompute it
+    if !same_scaling_mode(&projection.scaling_mode, &scaling_mode) {
+        projection.scaling_mode = scaling_mode;
+    }
+    if projection.viewport_origin != viewport_origin {
+        projection.viewport_origin = viewport_origin;
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::*;
+
+use crate::{
+    letterbox::bar_rects,
+    overlay::{to_overlay_space, Overlay},
+    SyncedViewport,
+};
+
+/// The spacing in physical pixels between the lines shading the letterbox bars.
+const BAR_SHADING_SPACING: f32 = 8.;
+
+/// The gizmo group the debug viewport outlines are drawn with, on the overlay render layer.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct FixedViewportGizmos;
+
+/// Attach this to a camera with a fixed viewport to outline its viewport and shade its letterbox bars.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct DebugViewportGizmo {
+    /// Whether the gizmo is drawn, for toggling it at runtime.
+    pub enabled: bool,
+    /// The color of the outline around the viewport.
+    pub outline_color: Color,
+    /// The color of the shading over the letterbox bars, which should be translucent.
+    pub bar_color: Color,
+}
+
+impl Default for DebugViewportGizmo {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            outline_color: Color::srgb(0., 1., 0.),
+            bar_color: Color::srgba(1., 0., 1., 0.25),
+        }
+    }
+}
+
+/// Draws the debug gizmo over the viewport the owner was just synced to, around the owner's overlay origin.
+pub(crate) fn draw_debug_gizmos(
+    owner_query: Query<(&DebugViewportGizmo, &Overlay, &SyncedViewport)>,
+    mut gizmos: Gizmos<FixedViewportGizmos>,
+) {
+    for (debug_gizmo, overlay, synced_viewport) in owner_query.iter() {
+        if !debug_gizmo.enabled {
+            continue;
+        }
+        let target_size = synced_viewport.target_size.as_vec2();
+        let to_world = |point: Vec2| overlay.origin + to_overlay_space(point, target_size);
+
+        // outline the viewport
+        let viewport = &synced_viewport.viewport;
+        let size = viewport.physical_size.as_vec2();
+        let center = viewport.physical_position.as_vec2() + size / 2.;
+        gizmos.rect_2d(to_world(center), 0., size, debug_gizmo.outline_color);
+
+        // shade the letterbox bars with evenly spaced lines
+        for rect in bar_rects(synced_viewport) {
+            if rect.is_empty() {
+                continue;
+            }
+            let mut y = rect.min.y + BAR_SHADING_SPACING / 2.;
+            while y < rect.max.y {
+                gizmos.line_2d(
+                    to_world(Vec2::new(rect.min.x, y)),
+                    to_world(Vec2::new(rect.max.x, y)),
+                    debug_gizmo.bar_color,
+                );
+                y += BAR_SHADING_SPACING;
+            }
+        }
+    }
+}
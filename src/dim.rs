@@ -0,0 +1,117 @@
+use bevy::{prelude::*, render::view::RenderLayers};
+
+use crate::{
+    overlay::{to_overlay_space, ManagedBy, Overlay, OVERLAY_RENDER_LAYER},
+    SyncedViewport,
+};
+
+/// Attach this to a camera with a fixed viewport to darken everything inside its viewport except a focus rect,
+/// drawn as an overlay that leaves the viewport itself untouched.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct ViewportDim {
+    /// The color drawn over the dimmed area, which should be translucent.
+    pub color: Color,
+    /// The area left undimmed, as fractions of the viewport from its top-left corner.
+    pub focus: Rect,
+}
+
+impl Default for ViewportDim {
+    fn default() -> Self {
+        Self {
+            color: Color::srgba(0., 0., 0., 0.5),
+            focus: Rect::new(0.25, 0.25, 0.75, 0.75),
+        }
+    }
+}
+
+/// The managed entities that draw a camera's dimming, on its overlay.
+#[derive(Component)]
+pub(crate) struct DimOverlay {
+    panels: [Entity; 4],
+}
+
+/// Spawns the managed panel entities for cameras that have just been given a dimming.
+pub(crate) fn spawn_dim_overlays(
+    camera_query: Query<(Entity, &ViewportDim, &Overlay), Without<DimOverlay>>,
+    mut commands: Commands,
+) {
+    for (entity, viewport_dim, overlay) in camera_query.iter() {
+        // the panels start empty and are sized once the owner's viewport is known
+        let panels = [(); 4].map(|_| {
+            commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: viewport_dim.color,
+                            custom_size: Some(Vec2::ZERO),
+                            ..default()
+                        },
+                        // below the letterbox bars, so feathered edges fade over the dimming
+                        transform: Transform::from_xyz(0., 0., -2.),
+                        ..default()
+                    },
+                    RenderLayers::layer(OVERLAY_RENDER_LAYER),
+                    ManagedBy::<ViewportDim>::new(entity),
+                ))
+                .set_parent(overlay.camera)
+                .id()
+        });
+
+        commands.entity(entity).insert(DimOverlay { panels });
+    }
+}
+
+/// Keeps the managed panel entities covering the owner's synced viewport around its focus rect.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_dim_overlays(
+    owner_query: Query<
+        (&ViewportDim, &DimOverlay, &SyncedViewport),
+        Or<(
+            Changed<SyncedViewport>,
+            Changed<ViewportDim>,
+            Added<DimOverlay>,
+        )>,
+    >,
+    mut panel_query: Query<(&mut Sprite, &mut Transform)>,
+) {
+    for (viewport_dim, dim_overlay, synced_viewport) in owner_query.iter() {
+        let target_size = synced_viewport.target_size.as_vec2();
+
+        for (panel, rect) in dim_overlay
+            .panels
+            .iter()
+            .zip(panel_rects(synced_viewport, viewport_dim.focus))
+        {
+            let (mut sprite, mut transform) = match panel_query.get_mut(*panel) {
+                Ok(item) => item,
+                Err(_) => continue,
+            };
+
+            let center = to_overlay_space(rect.center(), target_size);
+            sprite.color = viewport_dim.color;
+            sprite.custom_size = Some(rect.size());
+            transform.translation.x = center.x;
+            transform.translation.y = center.y;
+        }
+    }
+}
+
+/// The top, bottom, left and right panels dimming a synced viewport around a focus rect, in physical pixels.
+fn panel_rects(synced_viewport: &SyncedViewport, focus: Rect) -> [Rect; 4] {
+    let min = synced_viewport.viewport.physical_position.as_vec2();
+    let size = synced_viewport.viewport.physical_size.as_vec2();
+    let max = min + size;
+
+    // keep the focus inside the viewport, even when given out of range or inverted
+    let focus = Rect::from_corners(
+        min + focus.min.clamp(Vec2::ZERO, Vec2::ONE) * size,
+        min + focus.max.clamp(Vec2::ZERO, Vec2::ONE) * size,
+    );
+    [
+        Rect::new(min.x, min.y, max.x, focus.min.y),
+        Rect::new(min.x, focus.max.y, max.x, max.y),
+        Rect::new(min.x, focus.min.y, focus.min.x, focus.max.y),
+        Rect::new(focus.max.x, focus.min.y, max.x, focus.max.y),
+    ]
+}
@@ -0,0 +1,256 @@
+use bevy::{
+    ecs::entity::EntityHashSet,
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+        view::RenderLayers,
+    },
+};
+
+use std::f32::consts::FRAC_PI_2;
+
+use crate::{
+    overlay::{to_overlay_space, ManagedBy, Overlay, OVERLAY_RENDER_LAYER},
+    SyncedViewport,
+};
+
+/// Attach this to a camera with a fixed viewport to paint its letterbox bars a solid color every frame.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct LetterboxColor(pub Color);
+
+/// Attach this alongside a [`LetterboxColor`] to stretch an image over each letterbox bar, tinted by the color.
+///
+/// Use white as the color to show the image as it is.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct LetterboxImage(pub Handle<Image>);
+
+/// Attach this alongside a [`LetterboxColor`] to fade the bars into the viewport over the given number of
+/// physical pixels, softening the edge between them.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct LetterboxFeather(pub f32);
+
+/// The width of the gradient the feathered edges are drawn with.
+const FEATHER_GRADIENT_WIDTH: u32 = 32;
+
+/// The managed entities that draw a camera's letterbox bars, on its overlay.
+#[derive(Component)]
+pub(crate) struct LetterboxBars {
+    bars: [Entity; 4],
+    /// The edges fading each bar into the viewport, in the same order as the bars.
+    feathers: [Entity; 4],
+}
+
+/// Spawns the managed bar entities for cameras that have just been given a letterbox color.
+pub(crate) fn spawn_letterbox_bars(
+    camera_query: Query<(Entity, &LetterboxColor, &Overlay), Without<LetterboxBars>>,
+    mut images: Option<ResMut<Assets<Image>>>,
+    mut feather_gradient: Local<Option<Handle<Image>>>,
+    mut commands: Commands,
+) {
+    for (entity, letterbox_color, overlay) in camera_query.iter() {
+        // the bars are drawn by the overlay camera, as they sit outside the owner's viewport
+        let camera = overlay.camera;
+
+        // the bars start empty and are sized once the owner's viewport is known
+        let bars = [(); 4].map(|_| {
+            commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: letterbox_color.0,
+                            custom_size: Some(Vec2::ZERO),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(0., 0., -1.),
+                        ..default()
+                    },
+                    RenderLayers::layer(OVERLAY_RENDER_LAYER),
+                    ManagedBy::<LetterboxColor>::new(entity),
+                ))
+                .set_parent(camera)
+                .id()
+        });
+
+        // the feathered edges share a gradient, fading from opaque on the left to transparent on the right
+        let gradient = match (&*feather_gradient, &mut images) {
+            (Some(gradient), _) => gradient.clone(),
+            (None, Some(images)) => feather_gradient
+                .insert(images.add(feather_gradient_image()))
+                .clone(),
+            (None, None) => Handle::default(),
+        };
+        let feathers = [(); 4].map(|_| {
+            commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: letterbox_color.0,
+                            custom_size: Some(Vec2::ZERO),
+                            ..default()
+                        },
+                        texture: gradient.clone(),
+                        transform: Transform::from_xyz(0., 0., -1.),
+                        ..default()
+                    },
+                    RenderLayers::layer(OVERLAY_RENDER_LAYER),
+                    ManagedBy::<LetterboxColor>::new(entity),
+                ))
+                .set_parent(camera)
+                .id()
+        });
+
+        commands
+            .entity(entity)
+            .insert(LetterboxBars { bars, feathers });
+    }
+}
+
+/// Keeps the managed bar entities covering the space left around the owner's synced viewport.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_letterbox_bars(
+    owner_query: Query<(
+        Entity,
+        Ref<LetterboxColor>,
+        Option<Ref<LetterboxImage>>,
+        Option<Ref<LetterboxFeather>>,
+        Ref<LetterboxBars>,
+        Ref<SyncedViewport>,
+    )>,
+    mut removed_images: RemovedComponents<LetterboxImage>,
+    mut removed_feathers: RemovedComponents<LetterboxFeather>,
+    mut bar_query: Query<(&mut Sprite, &mut Handle<Image>, &mut Transform)>,
+) {
+    // bars whose image or feather was removed need to go back to plain bars
+    let removed: EntityHashSet = removed_images
+        .read()
+        .chain(removed_feathers.read())
+        .collect();
+
+    for (
+        entity,
+        letterbox_color,
+        letterbox_image,
+        letterbox_feather,
+        letterbox_bars,
+        synced_viewport,
+    ) in owner_query.iter()
+    {
+        if !synced_viewport.is_changed()
+            && !letterbox_color.is_changed()
+            && !letterbox_image
+                .as_ref()
+                .is_some_and(|image| image.is_changed())
+            && !letterbox_feather
+                .as_ref()
+                .is_some_and(|feather| feather.is_changed())
+            && !letterbox_bars.is_added()
+            && !removed.contains(&entity)
+        {
+            continue;
+        }
+
+        let target_size = synced_viewport.target_size.as_vec2();
+
+        for (bar, rect) in letterbox_bars.bars.iter().zip(bar_rects(&synced_viewport)) {
+            let (mut sprite, mut texture, mut transform) = match bar_query.get_mut(*bar) {
+                Ok(item) => item,
+                Err(_) => continue,
+            };
+
+            let center = to_overlay_space(rect.center(), target_size);
+            *texture = match &letterbox_image {
+                Some(letterbox_image) => letterbox_image.0.clone(),
+                None => Handle::default(),
+            };
+            sprite.color = letterbox_color.0;
+            sprite.custom_size = Some(rect.size());
+            transform.translation.x = center.x;
+            transform.translation.y = center.y;
+        }
+
+        // fade each bar that has any thickness into the viewport
+        let feather = letterbox_feather.map_or(0., |feather| feather.0.max(0.));
+        let bars = bar_rects(&synced_viewport);
+        let edges = feather_rects(&synced_viewport, feather);
+        for (index, feather) in letterbox_bars.feathers.iter().enumerate() {
+            let (mut sprite, _, mut transform) = match bar_query.get_mut(*feather) {
+                Ok(item) => item,
+                Err(_) => continue,
+            };
+
+            // the gradient fades to the right, so it's turned to fade away from each bar
+            let rect = edges[index];
+            let (size, rotation, flip_x) = match index {
+                0 => (Vec2::new(rect.height(), rect.width()), -FRAC_PI_2, false),
+                1 => (Vec2::new(rect.height(), rect.width()), FRAC_PI_2, false),
+                2 => (rect.size(), 0., false),
+                _ => (rect.size(), 0., true),
+            };
+            let center = to_overlay_space(rect.center(), target_size);
+            sprite.color = letterbox_color.0;
+            sprite.flip_x = flip_x;
+            sprite.custom_size = match bars[index].is_empty() {
+                true => Some(Vec2::ZERO),
+                false => Some(size),
+            };
+            transform.translation.x = center.x;
+            transform.translation.y = center.y;
+            transform.rotation = Quat::from_rotation_z(rotation);
+        }
+    }
+}
+
+/// The edges inside a synced viewport that fade the top, bottom, left and right bars into it, in physical pixels.
+fn feather_rects(synced_viewport: &SyncedViewport, feather: f32) -> [Rect; 4] {
+    let min = synced_viewport.viewport.physical_position.as_vec2();
+    let max = min + synced_viewport.viewport.physical_size.as_vec2();
+    let feather = Vec2::splat(feather).min((max - min) / 2.);
+    [
+        Rect::new(min.x, min.y, max.x, min.y + feather.y),
+        Rect::new(min.x, max.y - feather.y, max.x, max.y),
+        Rect::new(min.x, min.y, min.x + feather.x, max.y),
+        Rect::new(max.x - feather.x, min.y, max.x, max.y),
+    ]
+}
+
+/// A white gradient fading from opaque on the left to transparent on the right.
+fn feather_gradient_image() -> Image {
+    let data = (0..FEATHER_GRADIENT_WIDTH)
+        .flat_map(|x| {
+            let alpha = 255 - x * 255 / (FEATHER_GRADIENT_WIDTH - 1);
+            [255, 255, 255, alpha as u8]
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d {
+            width: FEATHER_GRADIENT_WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    // the gradient should stay smooth even when pixel art samples nearest by default
+    image.sampler = ImageSampler::linear();
+    image
+}
+
+/// The top, bottom, left and right letterbox bars within a synced viewport's region, in physical pixels.
+pub(crate) fn bar_rects(synced_viewport: &SyncedViewport) -> [Rect; 4] {
+    let region = synced_viewport.region.as_rect();
+    let min = synced_viewport.viewport.physical_position.as_vec2();
+    let max = min + synced_viewport.viewport.physical_size.as_vec2();
+    [
+        Rect::new(region.min.x, region.min.y, region.max.x, min.y),
+        Rect::new(region.min.x, max.y, region.max.x, region.max.y),
+        Rect::new(region.min.x, min.y, min.x, max.y),
+        Rect::new(max.x, min.y, region.max.x, max.y),
+    ]
+}
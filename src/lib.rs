@@ -1,44 +1,1010 @@
 use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    ecs::{
+        entity::EntityHashSet,
+        schedule::{InternedScheduleLabel, ScheduleLabel},
+        system::{SystemId, SystemParam},
+        world::Command,
+    },
+    gizmos::GizmoPlugin,
     prelude::*,
-    render::camera::{RenderTarget, Viewport},
-    window::{PrimaryWindow, WindowRef, WindowResized, WindowScaleFactorChanged},
+    render::{
+        camera::{ManualTextureViews, RenderTarget, ScalingMode, Viewport},
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        view::RenderLayers,
+    },
+    ui::UiSystem,
+    utils::HashMap,
+    window::{PrimaryWindow, WindowCreated, WindowRef, WindowResized, WindowScaleFactorChanged},
 };
-use itertools::Itertools;
 
-/// Contains functionality for fitting a fixed viewport aspect ratio to the window.
-pub struct FixedViewportPlugin;
+mod aspect;
+mod background;
+mod crop;
+mod debug;
+mod dim;
+mod letterbox;
+mod lock;
+mod overlay;
+mod param;
+mod registry;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod tween;
+mod ui;
+
+pub use aspect::FixedAspect;
+pub use background::BarClearColor;
+pub use debug::{DebugViewportGizmo, FixedViewportGizmos};
+pub use dim::ViewportDim;
+pub use letterbox::{LetterboxColor, LetterboxFeather, LetterboxImage};
+pub use lock::AspectRatioLocked;
+pub use overlay::OVERLAY_RENDER_LAYER;
+pub use param::FixedViewports;
+pub use registry::FixedViewportRegistry;
+pub use tween::FixedViewportTween;
+pub use ui::ViewportTrackingNode;
+
+/// Contains functionality for fitting a fixed viewport aspect ratio to the window or image being rendered to.
+pub struct FixedViewportPlugin {
+    /// The schedule viewports are synced in, along with the overlays drawn around them.
+    schedule: InternedScheduleLabel,
+}
+
+impl Default for FixedViewportPlugin {
+    fn default() -> Self {
+        Self::in_schedule(PostUpdate)
+    }
+}
+
+impl FixedViewportPlugin {
+    /// Syncs viewports in the given schedule instead of [`PostUpdate`],
+    /// for apps that move cameras in a schedule of their own.
+    ///
+    /// Viewport tracking UI nodes are still updated in [`PostUpdate`], as UI layout runs there.
+    pub fn in_schedule(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+        }
+    }
+
+    /// The diagnostic counting the viewports fitted each frame.
+    pub const SYNCS: DiagnosticPath = DiagnosticPath::const_new("fixed_viewport/syncs_per_frame");
+    /// The diagnostic counting the sync events each frame that couldn't be acted on,
+    /// such as for an unresolved render target.
+    pub const DROPPED_EVENTS: DiagnosticPath =
+        DiagnosticPath::const_new("fixed_viewport/dropped_events");
+}
 
 impl Plugin for FixedViewportPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<SyncEvent>().add_systems(
-            PostUpdate,
-            (
-                (emit_camera_sync_event, emit_window_sync_event),
-                sync_viewport,
+        let sync_system = app.world_mut().register_system(sync_requested_viewport);
+        app.insert_resource(SyncViewportSystem(sync_system))
+            .init_resource::<PrimaryWindowFallback>()
+            .register_diagnostic(Diagnostic::new(Self::SYNCS))
+            .register_diagnostic(Diagnostic::new(Self::DROPPED_EVENTS))
+            .register_type::<FixedViewport>()
+            .register_type::<AspectRatio>()
+            .register_type::<LetterboxColor>()
+            .register_type::<LetterboxImage>()
+            .register_type::<LetterboxFeather>()
+            .register_type::<AspectRatioLocked>()
+            .register_type::<BarClearColor>()
+            .register_type::<DebugViewportGizmo>()
+            .register_type::<ViewportDim>()
+            .register_type::<FixedViewportTween>()
+            .register_type::<CurrentViewport>()
+            .register_type::<TargetSizeOverride>()
+            .register_type::<FixedViewportGroup>()
+            .register_type::<ViewportUniform>()
+            .register_type::<ViewportScale>()
+            .register_type::<ViewportTrackingNode>()
+            .register_type::<SafeAreaInsets>()
+            .register_type::<AvailableViewportRect>()
+            .add_plugins(ExtractComponentPlugin::<ViewportUniform>::default())
+            // the window and asset events are read even when their plugins are missing, such as when headless
+            .add_event::<WindowCreated>()
+            .add_event::<WindowResized>()
+            .add_event::<WindowScaleFactorChanged>()
+            .add_event::<AssetEvent<Image>>()
+            .init_resource::<FixedViewportRegistry>()
+            .add_event::<SyncEvent>()
+            .add_event::<ViewportChanged>()
+            .add_systems(
+                Update,
+                (tween::tween_aspect_ratio, lock::snap_locked_windows),
             )
-                .chain(),
-        );
+            .add_systems(
+                self.schedule,
+                (
+                    (
+                        emit_camera_sync_event,
+                        emit_target_sync_event,
+                        emit_removed_sync_event,
+                        emit_group_sync_event,
+                        emit_activated_sync_event,
+                        emit_window_sync_event,
+                        emit_primary_window_sync_event,
+                        emit_target_change_sync_event,
+                        emit_image_sync_event,
+                        emit_texture_view_sync_event,
+                        emit_safe_area_sync_event,
+                    ),
+                    sync_viewport,
+                )
+                    .chain()
+                    .run_if(any_with_component::<FixedViewport>)
+                    .in_set(FixedViewportSet::Sync),
+            )
+            .add_systems(
+                self.schedule,
+                (reset_viewport, reset_despawned_window_viewport).in_set(FixedViewportSet::Sync),
+            )
+            // every overlay of a camera is drawn by the one overlay camera, spawned before them and despawned after
+            .add_systems(
+                self.schedule,
+                (
+                    overlay::spawn_overlay_cameras,
+                    overlay::sync_overlay_cameras,
+                )
+                    .chain()
+                    .after(FixedViewportSet::Sync),
+            )
+            .add_systems(
+                self.schedule,
+                overlay::despawn_overlay_cameras.after(FixedViewportSet::Sync),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    letterbox::spawn_letterbox_bars,
+                    letterbox::update_letterbox_bars,
+                    overlay::despawn_managed::<LetterboxColor, letterbox::LetterboxBars>,
+                )
+                    .chain()
+                    .after(overlay::spawn_overlay_cameras)
+                    .before(overlay::despawn_overlay_cameras),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    background::spawn_bar_clear_cameras,
+                    background::update_bar_clear_cameras,
+                    overlay::despawn_managed::<BarClearColor, background::BarClear>,
+                )
+                    .chain()
+                    .after(FixedViewportSet::Sync),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    dim::spawn_dim_overlays,
+                    dim::update_dim_overlays,
+                    overlay::despawn_managed::<ViewportDim, dim::DimOverlay>,
+                )
+                    .chain()
+                    .after(overlay::spawn_overlay_cameras)
+                    .before(overlay::despawn_overlay_cameras),
+            )
+            .add_systems(
+                self.schedule,
+                registry::update_registry.after(FixedViewportSet::Sync),
+            )
+            .add_systems(
+                PostUpdate,
+                ui::track_viewport_nodes
+                    .after(FixedViewportSet::Sync)
+                    .before(UiSystem::Layout),
+            );
     }
+
+    fn finish(&self, app: &mut App) {
+        // the debug gizmos can only be drawn when gizmos are available
+        if app.is_plugin_added::<GizmoPlugin>() {
+            app.insert_gizmo_config(
+                FixedViewportGizmos,
+                GizmoConfig {
+                    render_layers: RenderLayers::layer(OVERLAY_RENDER_LAYER),
+                    ..default()
+                },
+            )
+            .add_systems(
+                self.schedule,
+                debug::draw_debug_gizmos.after(overlay::spawn_overlay_cameras),
+            );
+        }
+    }
+}
+
+/// The system sets the plugin's systems run in, for ordering systems around them.
+#[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FixedViewportSet {
+    /// Syncs camera viewports to their render targets, in [`PostUpdate`] unless the plugin is given another schedule.
+    ///
+    /// However many events ask for a camera to be synced in a frame, such as a burst of resizes while a window
+    /// is dragged, it's only fitted once, to the latest size of its render target.
+    Sync,
 }
 
 /// The things that can cause a sync to happen.
-#[derive(Event)]
-enum SyncEvent {
+///
+/// Send [`SyncEvent::Camera`] to force a camera to be synced for changes the plugin doesn't track,
+/// such as from your own triggers. The event is read in [`FixedViewportSet::Sync`].
+#[derive(Event, Clone, Debug)]
+pub enum SyncEvent {
     /// The camera's fixed viewport has changed.
     Camera(Entity),
-    // The window was resized or the scale factor changed.
+    /// The camera's render target has changed in a way that may refit it.
+    Target(Entity),
+    /// The window was resized or the scale factor changed.
     Window(Entity),
+    /// The image was created or modified, which may have changed its size.
+    Image(AssetId<Image>),
+}
+
+/// Sent when a camera's viewport is changed by a sync.
+#[derive(Event, Clone, Debug)]
+pub struct ViewportChanged {
+    /// The camera whose viewport changed.
+    pub camera: Entity,
+    /// The newly applied viewport.
+    pub viewport: Viewport,
+}
+
+/// Triggered on a camera when a sync changes its viewport, for observers attached to the camera.
+///
+/// This is triggered alongside [`ViewportChanged`], so observe it with `commands.entity(camera).observe(...)` to react to a single camera.
+#[derive(Event, Clone, Debug)]
+pub struct ViewportSynced {
+    /// The newly applied viewport.
+    pub viewport: Viewport,
 }
 
 /// Attach this to a camera make the viewport fit the available window space with a fixed aspect ratio.
-#[derive(Component, Clone)]
+///
+/// The viewport is synced whenever this changes, so marking it as changed with [`Mut::set_changed`]
+/// forces a sync for changes the plugin can't detect itself.
+///
+/// A sync only ever writes [`Camera::viewport`], leaving the rest of the camera and its projection
+/// to other code, unless asked to lock the projection or the fit is cropped.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct FixedViewport {
-    pub aspect_ratio: f32,
+    /// The aspect ratio the viewport is fitted to, as a float or an exact width and height pair.
+    pub aspect_ratio: AspectRatio,
+    /// How the fitted viewport is scaled within the window.
+    ///
+    /// Like every other setting here, changing this alone refits the viewport the same frame.
+    pub mode: ScaleMode,
+    /// Where the fitted viewport sits within the leftover window space.
+    pub alignment: ViewportAlignment,
+    /// The smallest physical size the viewport is allowed to shrink to.
+    pub min_size: UVec2,
+    /// The narrowest aspect ratio the viewport may adapt to, defaulting to the fixed aspect ratio.
+    pub min_aspect_ratio: Option<f32>,
+    /// The widest aspect ratio the viewport may adapt to, defaulting to the fixed aspect ratio.
+    pub max_aspect_ratio: Option<f32>,
+    /// How the camera's orthographic projection is kept in step with the viewport.
+    pub projection: ProjectionLock,
+    /// The physical space kept free on each side of the window before fitting the viewport.
+    pub margin: ViewportMargin,
+    /// The normalized region of the window the viewport is fitted within, such as one half for split-screen.
+    ///
+    /// Every camera is fitted to its own region, so cameras sharing a window can be laid out independently,
+    /// such as a minimap inset in a corner drawn over the main camera with a higher [`Camera::order`].
+    pub region: Rect,
+    /// Whether the viewport is fitted in logical window pixels and only scaled to physical pixels at the end,
+    /// so integer scaling behaves the same across scale factors.
+    pub use_logical_size: bool,
+    /// Whether the viewport is fixed, letting the camera render to the whole window while disabled.
+    pub enabled: bool,
+    /// The aspect ratio used instead while the window is taller than it is wide, for devices that rotate.
+    pub portrait_aspect_ratio: Option<f32>,
+    /// Whether bars may be left above and below the viewport, or the viewport covers the height instead,
+    /// cropping the overflow as [`ScaleMode::Fill`] does.
+    pub allow_horizontal_bars: bool,
+    /// Whether bars may be left either side of the viewport, or the viewport covers the width instead,
+    /// cropping the overflow as [`ScaleMode::Fill`] does.
+    pub allow_vertical_bars: bool,
+    /// How the fitted size is rounded to whole physical pixels.
+    pub pixel_rounding: PixelRounding,
+    /// The largest scale of the base resolution the viewport may be drawn at, leaving larger bars instead.
+    ///
+    /// Only applies to scale modes with a base resolution.
+    pub max_scale: Option<u32>,
+    /// Whether the base resolution is never scaled up, leaving larger bars on large windows.
+    ///
+    /// Unlike a `max_scale` of one, the viewport is still scaled down to fit windows smaller than the base resolution.
+    /// Only applies to scale modes with a base resolution.
+    pub no_upscale: bool,
+    /// The width of the target's physical pixels relative to their height, for displays without square pixels.
+    ///
+    /// The viewport is fitted so the displayed image keeps the intended aspect ratio.
+    pub pixel_aspect: f32,
+    /// The fraction of the fitted size the viewport is shrunk to towards its center, leaving a border inside the fit.
+    ///
+    /// This is clamped between zero and one, where one uses the whole fitted size.
+    pub fill_fraction: f32,
+    /// Whether the viewport is only refitted when this changes, leaving it alone when the render target changes,
+    /// such as for a camera fitted once and then controlled manually.
+    pub manual: bool,
+    /// The grid cell size the viewport's size is rounded down to a multiple of, leaving slightly larger bars,
+    /// so tiles of that size are never cut at the edges.
+    pub grid_align: Option<u32>,
+    /// How far the available space's aspect ratio may be from the fixed aspect ratio and still be treated as a match,
+    /// using the whole space without bars, so a thin bar doesn't flicker in and out while resizing.
+    pub aspect_tolerance: f32,
+    /// The physical size of a virtual canvas to fit the viewport to instead of the render target,
+    /// which may be larger than the target, such as for a map the app scrolls around.
+    ///
+    /// The camera's own viewport is left for the app to offset and apply, with the viewport fitted to the canvas
+    /// available from [`CurrentViewport`] and [`ViewportChanged`]. Safe area insets don't apply to a canvas,
+    /// and overlays such as letterbox bars are drawn around the canvas viewport as if it were on the target.
+    pub canvas_size: Option<UVec2>,
+}
+
+impl Default for FixedViewport {
+    fn default() -> Self {
+        Self::WIDESCREEN_16_9
+    }
 }
 
-/// Emits a sync event when the camera's fixed viewport changes.
+impl FixedViewport {
+    /// A fixed viewport for widescreen 16:9 displays.
+    pub const WIDESCREEN_16_9: Self = Self::from_ratio(16, 9);
+    /// A fixed viewport for standard 4:3 displays.
+    pub const STANDARD_4_3: Self = Self::from_ratio(4, 3);
+    /// A fixed viewport for ultrawide 21:9 displays.
+    pub const ULTRAWIDE_21_9: Self = Self::from_ratio(21, 9);
+
+    /// A fixed viewport with the given aspect ratio and everything else left at its defaults.
+    pub const fn new(aspect_ratio: f32) -> Self {
+        Self::from_aspect_ratio(AspectRatio::Value(aspect_ratio))
+    }
+
+    /// A fixed viewport with the given float or exact aspect ratio and everything else left at its defaults.
+    pub const fn from_aspect_ratio(aspect_ratio: AspectRatio) -> Self {
+        Self {
+            aspect_ratio,
+            mode: ScaleMode::Fit,
+            alignment: ViewportAlignment::Center,
+            min_size: UVec2::ONE,
+            min_aspect_ratio: None,
+            max_aspect_ratio: None,
+            projection: ProjectionLock::None,
+            margin: ViewportMargin::ZERO,
+            region: Rect {
+                min: Vec2::ZERO,
+                max: Vec2::ONE,
+            },
+            use_logical_size: false,
+            enabled: true,
+            portrait_aspect_ratio: None,
+            allow_horizontal_bars: true,
+            allow_vertical_bars: true,
+            pixel_rounding: PixelRounding::Round,
+            max_scale: None,
+            no_upscale: false,
+            pixel_aspect: 1.,
+            fill_fraction: 1.,
+            manual: false,
+            grid_align: None,
+            aspect_tolerance: 0.,
+            canvas_size: None,
+        }
+    }
+
+    /// Sets how the fitted viewport is scaled within the window.
+    pub fn with_mode(mut self, mode: ScaleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets where the fitted viewport sits within the leftover window space.
+    pub fn with_alignment(mut self, alignment: ViewportAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets the physical space kept free on each side of the window.
+    pub fn with_margin(mut self, margin: ViewportMargin) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the narrowest aspect ratio the viewport may adapt to.
+    pub fn with_min_aspect(mut self, min_aspect_ratio: f32) -> Self {
+        self.min_aspect_ratio = Some(min_aspect_ratio);
+        self
+    }
+
+    /// Sets the widest aspect ratio the viewport may adapt to.
+    pub fn with_max_aspect(mut self, max_aspect_ratio: f32) -> Self {
+        self.max_aspect_ratio = Some(max_aspect_ratio);
+        self
+    }
+
+    /// A fixed viewport with the aspect ratio of the given resolution.
+    pub fn from_resolution(width: u32, height: u32) -> Self {
+        Self::from_ratio(width, height)
+    }
+
+    /// A fixed viewport with the exact aspect ratio of the given numerator and denominator, such as `16:9`.
+    pub const fn from_ratio(numerator: u32, denominator: u32) -> Self {
+        Self::from_aspect_ratio(AspectRatio::Ratio(numerator, denominator))
+    }
+
+    /// The fixed aspect ratio as a float.
+    pub fn fixed_aspect_ratio(&self) -> f32 {
+        self.aspect_ratio.value()
+    }
+
+    /// A fixed viewport for a fixed internal resolution, scaled in whole multiples of it.
+    pub fn fixed_resolution(width: u32, height: u32) -> Self {
+        Self {
+            mode: ScaleMode::Integer {
+                base_resolution: UVec2::new(width, height),
+            },
+            ..Self::from_resolution(width, height)
+        }
+    }
+
+    /// Whether the aspect ratios are all finite and positive, which is required to fit a viewport.
+    pub fn has_valid_aspect_ratio(&self) -> bool {
+        [
+            Some(self.fixed_aspect_ratio()),
+            Some(self.pixel_aspect),
+            self.portrait_aspect_ratio,
+            self.min_aspect_ratio,
+            self.max_aspect_ratio,
+        ]
+        .into_iter()
+        .flatten()
+        .all(|aspect_ratio| aspect_ratio.is_finite() && aspect_ratio > 0.)
+    }
+
+    /// The aspect ratio to fit to a target of the given aspect ratio.
+    ///
+    /// This is the target's own aspect ratio clamped between the minimum and maximum aspect ratios,
+    /// which is just the fixed aspect ratio for the target's orientation when neither is set.
+    pub fn effective_aspect_ratio(&self, target_ratio: f32) -> f32 {
+        let aspect_ratio = match self.portrait_aspect_ratio {
+            Some(portrait_aspect_ratio) if target_ratio < 1. => portrait_aspect_ratio,
+            _ => self.fixed_aspect_ratio(),
+        };
+        let min = self.min_aspect_ratio.unwrap_or(aspect_ratio);
+        let max = self.max_aspect_ratio.unwrap_or(aspect_ratio);
+        target_ratio.max(min).min(max)
+    }
+
+    /// The physical rect of the region the viewport is fitted within, for a render target of the given size.
+    pub fn region_rect(&self, target_size: UVec2) -> URect {
+        let target = target_size.as_vec2();
+        let min = (self.region.min.clamp(Vec2::ZERO, Vec2::ONE) * target).round();
+        let max = (self.region.max.clamp(Vec2::ZERO, Vec2::ONE) * target).round();
+        URect::from_corners(min.as_uvec2(), max.as_uvec2())
+    }
+
+    /// The physical rect of the region the viewport is fitted within, taken from the safe area of a render target
+    /// of the given size.
+    pub fn safe_region_rect(&self, target_size: UVec2, safe_area_insets: &SafeAreaInsets) -> URect {
+        let safe_area = safe_area_insets.safe_rect(target_size);
+        let region = self.region_rect(safe_area.size());
+        URect::from_corners(region.min + safe_area.min, region.max + safe_area.min)
+    }
+
+    /// The scale the base resolution is drawn at within the given viewport.
+    ///
+    /// Returns `None` when the scale mode has no base resolution.
+    pub fn scale_factor(&self, viewport: &Viewport) -> Option<f32> {
+        self.mode.base_resolution().map(|base_resolution| {
+            (viewport.physical_size.as_vec2() / base_resolution.max(UVec2::ONE).as_vec2())
+                .min_element()
+        })
+    }
+
+    /// Converts a physical window position into a position relative to the viewport's origin.
+    ///
+    /// Returns `None` when the position falls outside of the viewport, such as over a letterbox bar.
+    /// Cursor positions reported by [`Window`] are logical, so multiply them by the window's scale factor first.
+    pub fn window_to_viewport(window_position: Vec2, viewport: &Viewport) -> Option<Vec2> {
+        match Self::is_point_in_viewport(window_position, viewport) {
+            true => Some(window_position - viewport.physical_position.as_vec2()),
+            false => None,
+        }
+    }
+
+    /// Whether a physical window position falls inside the viewport rather than over a letterbox bar.
+    ///
+    /// The top and left edges are inside the viewport and the bottom and right edges are not,
+    /// so a position on the edge between two viewports is only ever inside one of them.
+    pub fn is_point_in_viewport(point: Vec2, viewport: &Viewport) -> bool {
+        let min = viewport.physical_position.as_vec2();
+        let max = min + viewport.physical_size.as_vec2();
+        point.cmpge(min).all() && point.cmplt(max).all()
+    }
+
+    /// Whether a logical window cursor position falls inside the camera's viewport rather than over a letterbox bar.
+    ///
+    /// A camera without a viewport covers its whole render target, so the cursor is always inside it.
+    pub fn is_cursor_in_viewport(camera: &Camera, window_cursor: Vec2) -> bool {
+        match (&camera.viewport, camera.target_scaling_factor()) {
+            (Some(viewport), Some(scale_factor)) => {
+                Self::is_point_in_viewport(window_cursor * scale_factor, viewport)
+            }
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+
+    /// Converts a physical position relative to the viewport's origin into a physical window position.
+    pub fn viewport_to_window(viewport_position: Vec2, viewport: &Viewport) -> Vec2 {
+        viewport_position + viewport.physical_position.as_vec2()
+    }
+
+    /// Converts a logical window cursor position into a 2D world position seen through the camera.
+    ///
+    /// Returns `None` when the cursor is over a letterbox bar, so clicks on the bars can be ignored.
+    pub fn viewport_cursor_to_world(
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        window_cursor: Vec2,
+    ) -> Option<Vec2> {
+        let viewport_position = match &camera.viewport {
+            Some(viewport) => {
+                let scale_factor = camera.target_scaling_factor()?;
+                Self::window_to_viewport(window_cursor * scale_factor, viewport)? / scale_factor
+            }
+            None => window_cursor,
+        };
+        camera.viewport_to_world_2d(camera_transform, viewport_position)
+    }
+
+    /// The world space rect visible through a 2D camera's viewport, for culling or spawning things just out of view.
+    ///
+    /// This covers only what's drawn inside the viewport, not the letterbox bars around it,
+    /// and bounds the whole view if the camera is rotated. It takes the global transform,
+    /// so cameras parented to a moving rig report where they actually are.
+    pub fn visible_world_rect(camera: &Camera, camera_transform: &GlobalTransform) -> Option<Rect> {
+        let size = camera.logical_viewport_size()?;
+        let mut rect: Option<Rect> = None;
+        for corner in [
+            Vec2::ZERO,
+            Vec2::new(size.x, 0.),
+            Vec2::new(0., size.y),
+            size,
+        ] {
+            let corner = camera.viewport_to_world_2d(camera_transform, corner)?;
+            rect = Some(match rect {
+                Some(rect) => rect.union_point(corner),
+                None => Rect::from_corners(corner, corner),
+            });
+        }
+        rect
+    }
+}
+
+/// The components needed to give a camera a fixed viewport, for spawning alongside a camera bundle.
+#[derive(Bundle, Clone, Default)]
+pub struct FixedViewportBundle {
+    pub fixed_viewport: FixedViewport,
+}
+
+/// The viewport currently applied to a camera with a fixed viewport, kept up to date by the plugin.
+///
+/// Unlike [`Camera::viewport`], this is never overwritten by other code, and it's only changed
+/// when a sync actually moves or resizes the viewport, so it can be relied on for change detection.
+///
+/// Picking and other cursor handling can use this with [`FixedViewport::window_to_viewport`]
+/// to correct window positions for the letterbox offset.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CurrentViewport(pub Viewport);
+
+/// The viewport currently applied to a camera with a fixed viewport, normalized to its render target.
+///
+/// This is kept up to date by the plugin and extracted into the render world, for shaders such as post-processing
+/// passes that mask the letterbox bars or only apply effects inside the viewport.
+#[derive(Component, Clone, Copy, Default, PartialEq, Debug, Reflect, ExtractComponent)]
+#[reflect(Component, Default)]
+pub struct ViewportUniform {
+    /// The top-left corner of the viewport, in UV space of the render target.
+    pub origin: Vec2,
+    /// The size of the viewport, in UV space of the render target.
+    pub size: Vec2,
+}
+
+impl ViewportUniform {
+    /// The viewport normalized to a render target of the given physical size.
+    fn new(viewport: &Viewport, target_size: UVec2) -> Self {
+        let uv_rect = viewport_uv_rect(viewport, target_size);
+        Self {
+            origin: uv_rect.min,
+            size: uv_rect.size(),
+        }
+    }
+}
+
+/// The scale the base resolution is currently drawn at by a camera with a fixed viewport, kept up to date by the plugin,
+/// for scaling things that aren't rendered through the camera, such as screen shake.
+///
+/// This is only present while the scale mode has a base resolution.
+#[derive(Component, Clone, Copy, PartialEq, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ViewportScale(pub f32);
+
+/// Attach this to a camera with a fixed viewport to fit it to the given physical size instead of its render target's.
+///
+/// This lets the plugin drive viewports without a windowing or rendering backend, such as in headless tests.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TargetSizeOverride(pub UVec2);
+
+/// Attach this to a camera with a fixed viewport to post-process the viewport it's fitted to,
+/// for layouts the scale modes don't cover, such as a custom snap or a platform specific inset.
+///
+/// The function is given the fitted viewport and the physical size it was fitted to, which is the canvas size
+/// when one is set and the render target's otherwise, and whatever it returns is kept within that size.
+/// It isn't applied while the fixed viewport is disabled.
+#[derive(Component)]
+pub struct ViewportAdjuster(pub Box<dyn Fn(Viewport, UVec2) -> Viewport + Send + Sync>);
+
+impl ViewportAdjuster {
+    /// An adjuster applying the given function.
+    pub fn new(adjust: impl Fn(Viewport, UVec2) -> Viewport + Send + Sync + 'static) -> Self {
+        Self(Box::new(adjust))
+    }
+
+    /// Applies the adjustment to a viewport fitted to the given size.
+    fn adjust(&self, viewport: Viewport, fit_size: UVec2) -> Viewport {
+        let mut viewport = (self.0)(viewport, fit_size);
+        // a viewport reaching past the target is an error when rendering
+        viewport.physical_size = viewport.physical_size.min(fit_size);
+        viewport.physical_position = viewport
+            .physical_position
+            .min(fit_size - viewport.physical_size);
+        viewport
+    }
+}
+
+/// Attach this to cameras with a fixed viewport to give every camera sharing the group id the same viewport,
+/// such as a background, world and UI camera layered on one window.
+///
+/// The group is always synced together in the same frame, with the viewport fitted once by its lowest ordered camera
+/// and applied to the rest as is, so they never show different viewports even for a frame.
+/// Cameras in a group rendering to targets of different sizes are fitted once per size instead,
+/// and disabled cameras render to their whole target without affecting the rest of the group.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FixedViewportGroup(pub u32);
+
+/// The viewport last applied to a camera by a sync.
+#[derive(Component)]
+pub(crate) struct SyncedViewport {
+    viewport: Viewport,
+    /// The size of the render target the viewport was fitted to.
+    target_size: UVec2,
+    /// The physical region of the render target the viewport was fitted within.
+    region: URect,
+    /// The render target the viewport was fitted to, for noticing when the camera moves to another.
+    target: RenderTarget,
+    /// The scale factor of the render target when the viewport was fitted.
+    scale_factor: f32,
+}
+
+/// Marks a camera already warned about for having a render target that couldn't be resolved.
+#[derive(Component)]
+struct UnresolvedTargetWarned;
+
+/// A fixed aspect ratio, either as a float or as an exact width and height pair.
+#[derive(Clone, Copy, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AspectRatio {
+    /// The aspect ratio as a float, such as `16. / 9.`, which is simplest to animate or nudge.
+    Value(f32),
+    /// The aspect ratio as an exact width and height pair, such as `16:9`, which doesn't drift through edits.
+    Ratio(u32, u32),
+}
+
+impl AspectRatio {
+    /// The aspect ratio as a float.
+    pub fn value(&self) -> f32 {
+        match self {
+            AspectRatio::Value(aspect_ratio) => *aspect_ratio,
+            AspectRatio::Ratio(width, height) => *width as f32 / *height as f32,
+        }
+    }
+
+    /// Whether both are the same aspect ratio, however they're written, so `1920:1080` matches `16:9`.
+    pub fn matches(&self, other: &AspectRatio) -> bool {
+        // a ratio is correctly rounded from its exact value, so equal ratios are equal floats
+        self.value() == other.value()
+    }
+}
+
+impl From<f32> for AspectRatio {
+    fn from(aspect_ratio: f32) -> Self {
+        AspectRatio::Value(aspect_ratio)
+    }
+}
+
+/// The ways a fixed viewport can be scaled to the available window space.
+#[derive(Clone, Default, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScaleMode {
+    /// Scale the viewport to the largest size that fits the window.
+    #[default]
+    Fit,
+    /// Scale the viewport to the largest whole multiple of a base resolution that fits the window.
+    ///
+    /// The viewport is never scaled below `1x`, even when the window is smaller than the base resolution,
+    /// where it's clipped to the window and the camera's orthographic projection cropped as [`ScaleMode::Fill`] does.
+    Integer { base_resolution: UVec2 },
+    /// Scale the viewport to the smallest size that covers the window, cropping the overflow.
+    ///
+    /// Viewports cannot extend past the render target, so the viewport covers the window and the camera's
+    /// orthographic projection is cropped instead, to the part of the covering image left inside the window
+    /// as placed by the alignment. The projection's scaling mode and origin are managed while cropped,
+    /// and put back once it isn't.
+    Fill,
+    /// Stretch the viewport over the whole window, ignoring the aspect ratio.
+    Stretch,
+    /// Scale the viewport to the largest of the allowed scales of a base resolution that fits the window.
+    ///
+    /// When none of the scales fit, the smallest is used and the viewport is clipped.
+    SnapSteps {
+        base_resolution: UVec2,
+        steps: Vec<f32>,
+    },
+    /// Lock each given axis to a fixed extent, letting the others fill the window, ignoring the aspect ratio.
+    ///
+    /// The extents are in physical pixels, or logical pixels when fitting in logical pixels,
+    /// and are never larger than the window.
+    FixedExtent {
+        width: Option<u32>,
+        height: Option<u32>,
+    },
+}
+
+impl ScaleMode {
+    /// The base resolution the viewport is scaled from, if the mode has one.
+    pub fn base_resolution(&self) -> Option<UVec2> {
+        match self {
+            ScaleMode::Integer { base_resolution } => Some(*base_resolution),
+            ScaleMode::SnapSteps {
+                base_resolution, ..
+            } => Some(*base_resolution),
+            _ => None,
+        }
+    }
+}
+
+/// Physical pixel distances from each side of the window.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewportMargin {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+impl ViewportMargin {
+    /// No margin on any side.
+    pub const ZERO: Self = Self::all(0);
+
+    /// The same margin on every side.
+    pub const fn all(value: u32) -> Self {
+        Self {
+            left: value,
+            right: value,
+            top: value,
+            bottom: value,
+        }
+    }
+}
+
+/// Physical pixel insets of the window's safe area, such as around a phone's notch or rounded corners.
+///
+/// Bevy doesn't expose these, so insert and update this resource from the platform to keep the viewports
+/// of window cameras within the safe area.
+#[derive(Resource, Clone, Copy, Default, PartialEq, Eq, Debug, Reflect)]
+#[reflect(Resource, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SafeAreaInsets {
+    pub top: u32,
+    pub bottom: u32,
+    pub left: u32,
+    pub right: u32,
+}
+
+impl SafeAreaInsets {
+    /// The rect left inside the insets, for a render target of the given size.
+    pub fn safe_rect(&self, target_size: UVec2) -> URect {
+        let min = UVec2::new(self.left, self.top).min(target_size);
+        let max = target_size
+            .saturating_sub(UVec2::new(self.right, self.bottom))
+            .max(min);
+        URect::from_corners(min, max)
+    }
+
+    /// The larger of the two insets on each side.
+    fn max(self, other: Self) -> Self {
+        Self {
+            top: self.top.max(other.top),
+            bottom: self.bottom.max(other.bottom),
+            left: self.left.max(other.left),
+            right: self.right.max(other.right),
+        }
+    }
+
+    /// The insets in logical pixels, for fitting in logical pixels.
+    fn to_logical(self, scale_factor: f32) -> Self {
+        let scale = |inset: u32| (inset as f32 / scale_factor).round() as u32;
+        Self {
+            top: scale(self.top),
+            bottom: scale(self.bottom),
+            left: scale(self.left),
+            right: scale(self.right),
+        }
+    }
+}
+
+/// The physical pixel rect of the window left for the viewport, such as the central area between docked UI panels.
+///
+/// Insert and update this resource each frame to fit the viewports of window cameras within it instead of the whole
+/// window, combined with any [`SafeAreaInsets`]. UI libraries usually report logical rects, so multiply those by the
+/// window's scale factor first.
+#[derive(Resource, Clone, Copy, Default, PartialEq, Debug, Reflect)]
+#[reflect(Resource, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AvailableViewportRect(pub Rect);
+
+impl AvailableViewportRect {
+    /// The insets leaving only the rect, for a render target of the given size.
+    fn to_insets(self, target_size: UVec2) -> SafeAreaInsets {
+        let target = target_size.as_vec2();
+        let min = self.0.min.clamp(Vec2::ZERO, target).round().as_uvec2();
+        let max = self.0.max.clamp(Vec2::ZERO, target).round().as_uvec2();
+        SafeAreaInsets {
+            top: min.y,
+            bottom: target_size.y - max.y,
+            left: min.x,
+            right: target_size.x - max.x,
+        }
+    }
+}
+
+/// The window fixed viewports fall back to when more than one window is tagged as the primary window,
+/// set once the plugin has warned about it.
+///
+/// Bevy expects a single primary window, so this stays empty unless another is tagged by mistake.
+#[derive(Resource, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct PrimaryWindowFallback(pub Option<Entity>);
+
+/// The ways a camera's orthographic projection can be kept in step with its fixed viewport.
+#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProjectionLock {
+    /// Leave the projection alone, for cameras that manage their own.
+    #[default]
+    None,
+    /// Always show the given area of the world, whatever size the viewport is scaled to.
+    WorldSize(Vec2),
+    /// Show exactly the scale mode's base resolution in world units, so one world unit is always one base pixel.
+    ///
+    /// The projection is left alone under scale modes without a base resolution.
+    BaseResolution,
+}
+
+impl ProjectionLock {
+    /// The scaling mode that shows the locked area of the world under the given scale mode.
+    pub fn scaling_mode(&self, mode: &ScaleMode) -> Option<ScalingMode> {
+        match self {
+            ProjectionLock::None => None,
+            ProjectionLock::WorldSize(world_size) => Some(match mode {
+                // stretching deliberately distorts the world to the window
+                ScaleMode::Stretch => ScalingMode::Fixed {
+                    width: world_size.x,
+                    height: world_size.y,
+                },
+                _ => ScalingMode::AutoMin {
+                    min_width: world_size.x,
+                    min_height: world_size.y,
+                },
+            }),
+            ProjectionLock::BaseResolution => {
+                mode.base_resolution()
+                    .map(|base_resolution| ScalingMode::Fixed {
+                        width: base_resolution.x as f32,
+                        height: base_resolution.y as f32,
+                    })
+            }
+        }
+    }
+}
+
+/// The places a fixed viewport can be aligned to within the leftover window space.
+#[derive(Clone, Copy, Default, PartialEq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ViewportAlignment {
+    #[default]
+    Center,
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+    /// Shift the viewport towards a normalized point of the leftover space, where `(0.5, 0.5)` is centered,
+    /// for reframing the viewport smoothly such as during cinematics.
+    ///
+    /// The point is clamped to the leftover space, so the viewport never leaves the window.
+    FocalPoint(Vec2),
+}
+
+impl ViewportAlignment {
+    /// The normalized position of the viewport within the leftover space, where `(0, 0)` is the top-left.
+    pub fn anchor(&self) -> Vec2 {
+        match self {
+            ViewportAlignment::Center => Vec2::new(0.5, 0.5),
+            ViewportAlignment::TopLeft => Vec2::new(0., 0.),
+            ViewportAlignment::Top => Vec2::new(0.5, 0.),
+            ViewportAlignment::TopRight => Vec2::new(1., 0.),
+            ViewportAlignment::Left => Vec2::new(0., 0.5),
+            ViewportAlignment::Right => Vec2::new(1., 0.5),
+            ViewportAlignment::BottomLeft => Vec2::new(0., 1.),
+            ViewportAlignment::Bottom => Vec2::new(0.5, 1.),
+            ViewportAlignment::BottomRight => Vec2::new(1., 1.),
+            ViewportAlignment::FocalPoint(point) => point.clamp(Vec2::ZERO, Vec2::ONE),
+        }
+    }
+}
+
+/// The ways a fitted viewport size can be rounded to whole physical pixels.
+///
+/// The bars are split from whatever space the rounded size leaves, with any odd pixel going to the far bar,
+/// so rounding up leaves thinner bars and rounding down leaves thicker ones, but they're only ever symmetric
+/// when the leftover space is even.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PixelRounding {
+    /// Round to the nearest pixel.
+    #[default]
+    Round,
+    /// Round down, so the viewport never covers a partial pixel of content.
+    Floor,
+    /// Round up, so the viewport never leaves a partial pixel of content out, without growing past the available space.
+    Ceil,
+}
+
+impl PixelRounding {
+    /// Rounds the value to a whole pixel.
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            PixelRounding::Round => value.round(),
+            PixelRounding::Floor => value.floor(),
+            PixelRounding::Ceil => value.ceil(),
+        }
+    }
+}
+
+/// Emits a sync event when the camera's fixed viewport or target size override changes.
+#[allow(clippy::type_complexity)]
 fn emit_camera_sync_event(
-    camera_query: Query<Entity, Changed<FixedViewport>>,
+    camera_query: Query<
+        Entity,
+        Or<(
+            Changed<FixedViewport>,
+            (Changed<TargetSizeOverride>, With<FixedViewport>),
+            (Changed<FixedViewportGroup>, With<FixedViewport>),
+            (Changed<ViewportAdjuster>, With<FixedViewport>),
+        )>,
+    >,
     mut sync_events: EventWriter<SyncEvent>,
 ) {
     for entity in camera_query.iter() {
@@ -46,12 +1012,78 @@ fn emit_camera_sync_event(
     }
 }
 
-/// Emits a sync event when the window's size or scale factor changes.
+/// Emits a sync event when a camera loses its viewport adjuster, which leaves its viewport stale.
+fn emit_removed_sync_event(
+    mut removed_adjusters: RemovedComponents<ViewportAdjuster>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    for entity in removed_adjusters.read() {
+        sync_events.send(SyncEvent::Camera(entity));
+    }
+}
+
+/// Emits a sync event for every grouped camera when a camera joins, leaves or changes group,
+/// or a grouped camera loses its fixed viewport, as the rest of its former group may have taken their viewport from it.
+#[allow(clippy::type_complexity)]
+fn emit_group_sync_event(
+    mut removed_groups: RemovedComponents<FixedViewportGroup>,
+    mut removed_fixed_viewports: RemovedComponents<FixedViewport>,
+    changed_query: Query<(), (Changed<FixedViewportGroup>, With<FixedViewport>)>,
+    grouped_query: Query<(Entity, &FixedViewport), With<FixedViewportGroup>>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    // removed fixed viewports are only read to notice them, the camera itself is reset elsewhere
+    let removed_groups: Vec<Entity> = removed_groups.read().collect();
+    let removed_any = removed_fixed_viewports.read().count() > 0;
+    if removed_groups.is_empty() && !removed_any && changed_query.is_empty() {
+        return;
+    }
+    // manual cameras keep their viewport until they're changed themselves
+    let grouped = grouped_query
+        .iter()
+        .filter(|(_, fixed_viewport)| !fixed_viewport.manual)
+        .map(|(entity, _)| entity);
+    for entity in removed_groups.into_iter().chain(grouped) {
+        sync_events.send(SyncEvent::Camera(entity));
+    }
+}
+
+/// Emits a sync event when the camera is moved to a different render target than it was synced to,
+/// or changed at all before it was ever synced, such as when its first target couldn't be resolved.
+#[allow(clippy::type_complexity)]
+fn emit_target_sync_event(
+    camera_query: Query<
+        (Entity, &Camera, Option<&SyncedViewport>),
+        (Changed<Camera>, With<FixedViewport>),
+    >,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    for (entity, camera, synced_viewport) in camera_query.iter() {
+        // the camera changes for plenty of reasons besides its target, so only the target is compared
+        let retargeted = !synced_viewport
+            .is_some_and(|synced_viewport| same_target(&camera.target, &synced_viewport.target));
+        if retargeted {
+            sync_events.send(SyncEvent::Camera(entity));
+        }
+    }
+}
+
+/// Emits a sync event when the window is spawned, created or its size or scale factor changes.
 fn emit_window_sync_event(
+    window_query: Query<Entity, Added<Window>>,
+    mut created_events: EventReader<WindowCreated>,
     mut resize_events: EventReader<WindowResized>,
     mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut sync_events: EventWriter<SyncEvent>,
 ) {
+    // cameras spawned before their window existed need a sync once it does
+    for entity in window_query.iter() {
+        sync_events.send(SyncEvent::Window(entity));
+    }
+    // cameras spawned before the window reported its real size need a sync once it does
+    for event in created_events.read() {
+        sync_events.send(SyncEvent::Window(event.window));
+    }
     for event in resize_events.read() {
         sync_events.send(SyncEvent::Window(event.window));
     }
@@ -60,96 +1092,1060 @@ fn emit_window_sync_event(
     }
 }
 
-/// Synchronizes camera's viewport with the window size according to the fixed viewport.
-fn sync_viewport(
-    mut sync_events: EventReader<SyncEvent>,
-    mut camera_query: Query<(&FixedViewport, &mut Camera)>,
-    window_query: Query<(&Window, Option<&PrimaryWindow>)>,
+/// Emits a sync event when an inactive camera is made active again, as its syncs were skipped while inactive.
+#[allow(clippy::type_complexity)]
+fn emit_activated_sync_event(
+    camera_query: Query<(Entity, &Camera), (Changed<Camera>, With<FixedViewport>)>,
+    mut inactive: Local<EntityHashSet>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    for (entity, camera) in camera_query.iter() {
+        match camera.is_active {
+            true => {
+                if inactive.remove(&entity) {
+                    sync_events.send(SyncEvent::Camera(entity));
+                }
+            }
+            false => {
+                inactive.insert(entity);
+            }
+        }
+    }
+}
+
+/// Emits a sync event for the primary window when the primary window marker moves to another window.
+fn emit_primary_window_sync_event(
+    added_query: Query<Entity, Added<PrimaryWindow>>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    mut removed: RemovedComponents<PrimaryWindow>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    // cameras targeting the primary window need to refit to whichever window is primary now
+    let removed_any = removed.read().count() > 0;
+    let primary_windows: Vec<Entity> = match removed_any {
+        true => primary_window_query.iter().collect(),
+        false => added_query.iter().collect(),
+    };
+    for entity in primary_windows {
+        sync_events.send(SyncEvent::Window(entity));
+    }
+}
+
+/// Emits a sync event for cameras whose render target has changed size or scale factor since they were synced.
+///
+/// This compares against the size cached by the last sync every frame, so viewports heal themselves if an event
+/// is missed, and changes made directly to a window, such as a scale factor override, are caught the same frame.
+fn emit_target_change_sync_event(
+    camera_query: Query<(Entity, &SyncedViewport), Without<TargetSizeOverride>>,
+    mut render_targets: RenderTargets,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    for (entity, synced_viewport) in camera_query.iter() {
+        let target = &synced_viewport.target;
+        let resized = render_targets
+            .size(target)
+            .is_some_and(|target_size| target_size != synced_viewport.target_size);
+        if resized || render_targets.scale_factor(target) != synced_viewport.scale_factor {
+            sync_events.send(SyncEvent::Target(entity));
+        }
+    }
+}
+
+/// Emits a sync event when an image that may be used as a render target is created or modified.
+fn emit_image_sync_event(
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    for event in image_events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                sync_events.send(SyncEvent::Image(*id));
+            }
+            _ => continue,
+        }
+    }
+}
+
+/// Emits a sync event for cameras rendering to windows when the safe area insets or available rect change or are removed.
+fn emit_safe_area_sync_event(
+    safe_area_insets: Option<Res<SafeAreaInsets>>,
+    available_rect: Option<Res<AvailableViewportRect>>,
+    mut had_safe_area_insets: Local<bool>,
+    mut had_available_rect: Local<bool>,
+    camera_query: Query<(Entity, &Camera), With<FixedViewport>>,
+    mut sync_events: EventWriter<SyncEvent>,
 ) {
+    // removing either gives back the whole window, so it's as much a change as updating it
+    let safe_area_insets_removed = *had_safe_area_insets && safe_area_insets.is_none();
+    let available_rect_removed = *had_available_rect && available_rect.is_none();
+    *had_safe_area_insets = safe_area_insets.is_some();
+    *had_available_rect = available_rect.is_some();
+
+    if !safe_area_insets.is_some_and(|safe_area_insets| safe_area_insets.is_changed())
+        && !available_rect.is_some_and(|available_rect| available_rect.is_changed())
+        && !safe_area_insets_removed
+        && !available_rect_removed
+    {
+        return;
+    }
+    for (entity, camera) in camera_query.iter() {
+        if let RenderTarget::Window(_) = camera.target {
+            sync_events.send(SyncEvent::Target(entity));
+        }
+    }
+}
+
+/// Emits a sync event for cameras rendering to manual texture views when the views change.
+fn emit_texture_view_sync_event(
+    manual_texture_views: Option<Res<ManualTextureViews>>,
+    camera_query: Query<(Entity, &Camera), With<FixedViewport>>,
+    mut sync_events: EventWriter<SyncEvent>,
+) {
+    if !manual_texture_views.is_some_and(|manual_texture_views| manual_texture_views.is_changed()) {
+        return;
+    }
+    for (entity, camera) in camera_query.iter() {
+        if let RenderTarget::TextureView(_) = camera.target {
+            sync_events.send(SyncEvent::Target(entity));
+        }
+    }
+}
+
+/// The window a primary window reference resolves to, given the windows tagged as primary and all windows.
+pub(crate) fn primary_window_entity(
+    primary_windows: impl Iterator<Item = Entity>,
+    mut windows: impl Iterator<Item = Entity>,
+) -> Option<Entity> {
+    // if more than one primary window, fall back to the one with the lowest entity
+    match primary_windows.min() {
+        Some(window_entity) => Some(window_entity),
+        // without any primary window, such as during setup, fall back to the only window there is
+        None => match (windows.next(), windows.next()) {
+            (Some(window_entity), None) => Some(window_entity),
+            _ => None,
+        },
+    }
+}
+
+/// The render targets a fixed viewport can be fitted to.
+#[derive(SystemParam)]
+struct RenderTargets<'w, 's> {
+    window_query: Query<'w, 's, (Entity, &'static Window)>,
+    primary_window_query: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    images: Option<Res<'w, Assets<Image>>>,
+    manual_texture_views: Option<Res<'w, ManualTextureViews>>,
+    safe_area_insets: Option<Res<'w, SafeAreaInsets>>,
+    available_rect: Option<Res<'w, AvailableViewportRect>>,
+    primary_window_fallback: ResMut<'w, PrimaryWindowFallback>,
+}
+
+impl RenderTargets<'_, '_> {
+    /// The window a window render target refers to, if it can be resolved.
+    fn window(&mut self, window_ref: &WindowRef) -> Option<&Window> {
+        let window_entity = match window_ref {
+            WindowRef::Primary => {
+                let window_entity = primary_window_entity(
+                    self.primary_window_query.iter(),
+                    self.window_query
+                        .iter()
+                        .map(|(window_entity, _)| window_entity),
+                )?;
+                // warn once for each window fallen back to when more than one is primary
+                if self.primary_window_query.iter().count() > 1
+                    && self.primary_window_fallback.0 != Some(window_entity)
+                {
+                    warn!(
+                        "multiple primary windows found, falling back to {:?} for fixed viewports",
+                        window_entity
+                    );
+                    self.primary_window_fallback.0 = Some(window_entity);
+                }
+                window_entity
+            }
+            WindowRef::Entity(entity) => *entity,
+        };
+        self.window_query
+            .get(window_entity)
+            .ok()
+            .map(|(_, window)| window)
+    }
+
+    /// The physical size of the render target, if it can be resolved.
+    fn size(&mut self, target: &RenderTarget) -> Option<UVec2> {
+        match target {
+            RenderTarget::Window(window_ref) => {
+                self.window(window_ref).map(|window| window.physical_size())
+            }
+            RenderTarget::Image(handle) => self
+                .images
+                .as_ref()
+                .and_then(|images| images.get(handle))
+                .map(|image| image.size()),
+            RenderTarget::TextureView(handle) => self
+                .manual_texture_views
+                .as_ref()
+                .and_then(|manual_texture_views| manual_texture_views.get(handle))
+                .map(|manual_texture_view| manual_texture_view.size),
+        }
+    }
+
+    /// The safe area insets of the render target, which only windows have, shrunk to the available rect if any.
+    fn safe_area_insets(&self, target: &RenderTarget, target_size: UVec2) -> SafeAreaInsets {
+        let safe_area_insets = match (target, &self.safe_area_insets) {
+            (RenderTarget::Window(_), Some(safe_area_insets)) => **safe_area_insets,
+            _ => SafeAreaInsets::default(),
+        };
+        match (target, &self.available_rect) {
+            (RenderTarget::Window(_), Some(available_rect)) => {
+                safe_area_insets.max(available_rect.to_insets(target_size))
+            }
+            _ => safe_area_insets,
+        }
+    }
+
+    /// The ratio of physical to logical pixels of the render target, which is only ever not one for windows.
+    fn scale_factor(&mut self, target: &RenderTarget) -> f32 {
+        match target {
+            RenderTarget::Window(window_ref) => self
+                .window(window_ref)
+                .map(|window| window.scale_factor())
+                .unwrap_or(1.),
+            _ => 1.,
+        }
+    }
+}
+
+/// Synchronizes camera's viewport with the render target size according to the fixed viewport.
+fn sync_viewport(mut sync_events: EventReader<SyncEvent>, mut syncer: ViewportSyncer) {
+    let ViewportSyncer {
+        camera_query,
+        render_targets,
+        logged,
+        ..
+    } = &mut syncer;
+    // count the events that couldn't be acted on, for the diagnostics
+    let mut dropped_events = 0;
+
+    // collect the cameras that need syncing, so each is only synced once per frame,
+    // to the size its target has now rather than the size any one event saw
+    let mut entities = EntityHashSet::default();
     for event in sync_events.read() {
-        // resolve the required data for the syncing
-        let (window, mut cameras) = match event {
-            // the event came from the camera, find the matching window
-            SyncEvent::Camera(entity) => match camera_query.get_mut(*entity) {
-                Ok((fixed_viewport, camera)) => (
-                    match &camera.target {
-                        RenderTarget::Window(window_ref) => match window_ref {
-                            // if more than one primary window (extremely likely), we cannot continue
-                            WindowRef::Primary => match window_query
-                                .iter()
-                                .filter(|(_, primary_window)| primary_window.is_some())
-                                .exactly_one()
-                            {
-                                Ok((window, _)) => window,
-                                Err(_) => continue,
-                            },
-                            WindowRef::Entity(entity) => match window_query.get(*entity) {
-                                Ok((window, _)) => window,
-                                Err(_) => continue,
-                            },
-                        },
-                        _ => continue,
-                    },
-                    vec![(fixed_viewport, camera)],
-                ),
-                Err(_) => continue,
+        let matching: Vec<Entity> = match event {
+            // the event came from the camera, only that camera needs syncing
+            SyncEvent::Camera(entity) => vec![*entity],
+            // the event came from the camera's target, only that camera needs syncing unless it's manual
+            SyncEvent::Target(entity) => match camera_query.get(*entity) {
+                Ok((_, fixed_viewport, ..)) if fixed_viewport.manual => continue,
+                _ => vec![*entity],
             },
-            // the event came from the window, find the matching camera
+            // the event came from the window, find the matching cameras
             SyncEvent::Window(entity) => {
                 // get the window data first
-                let (window, primary_window) = match window_query.get(*entity) {
-                    Ok(item) => item,
-                    Err(_) => continue,
-                };
+                if !render_targets.window_query.contains(*entity) {
+                    if logged.missing_windows.insert(*entity) {
+                        debug!("dropped a sync event for the missing window {:?}", entity);
+                    }
+                    dropped_events += 1;
+                    continue;
+                }
+                // the window stands in for the primary window as the render targets resolve it
+                let primary_window = primary_window_entity(
+                    render_targets.primary_window_query.iter(),
+                    render_targets
+                        .window_query
+                        .iter()
+                        .map(|(window_entity, _)| window_entity),
+                ) == Some(*entity);
 
-                // find all matching cameras
-                (
-                    window,
-                    camera_query
-                        .iter_mut()
-                        .filter_map(|(fixed_viewport, camera)| match camera.target {
-                            RenderTarget::Window(window_ref) => match window_ref {
-                                WindowRef::Primary => match primary_window {
-                                    Some(_) => Some((fixed_viewport, camera)),
-                                    None => None,
-                                },
-                                WindowRef::Entity(ref_entity) => match ref_entity == *entity {
-                                    true => Some((fixed_viewport, camera)),
-                                    false => None,
-                                },
+                camera_query
+                    .iter()
+                    .filter(|(_, fixed_viewport, ..)| !fixed_viewport.manual)
+                    .filter_map(|(camera_entity, _, camera, ..)| match camera.target {
+                        RenderTarget::Window(window_ref) => match window_ref {
+                            WindowRef::Primary => primary_window.then_some(camera_entity),
+                            WindowRef::Entity(ref_entity) => match ref_entity == *entity {
+                                true => Some(camera_entity),
+                                false => None,
                             },
-                            _ => None,
-                        })
-                        .collect(),
-                )
+                        },
+                        _ => None,
+                    })
+                    .collect()
             }
+            // the event came from the image, find the matching cameras
+            SyncEvent::Image(id) => camera_query
+                .iter()
+                .filter(|(_, fixed_viewport, ..)| !fixed_viewport.manual)
+                .filter_map(|(camera_entity, _, camera, ..)| match &camera.target {
+                    RenderTarget::Image(handle) => match handle.id() == *id {
+                        true => Some(camera_entity),
+                        false => None,
+                    },
+                    _ => None,
+                })
+                .collect(),
         };
+        entities.extend(matching);
+    }
+
+    syncer.sync(entities, dropped_events);
+}
+
+/// Syncs just the requested camera, along with the rest of its group, for syncs on demand.
+fn sync_requested_viewport(In(camera): In<Entity>, mut syncer: ViewportSyncer) {
+    let mut entities = EntityHashSet::default();
+    entities.insert(camera);
+    syncer.sync(entities, 0);
+}
+
+/// Everything a sync needs to fit cameras to their render targets, shared by the scheduled and on demand syncs.
+#[derive(SystemParam)]
+struct ViewportSyncer<'w, 's> {
+    #[allow(clippy::type_complexity)]
+    camera_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static FixedViewport,
+            &'static mut Camera,
+            Option<&'static mut OrthographicProjection>,
+            Option<&'static crop::UncroppedProjection>,
+            Option<&'static mut CurrentViewport>,
+            Has<UnresolvedTargetWarned>,
+            Option<&'static TargetSizeOverride>,
+            Option<&'static FixedViewportGroup>,
+            Option<&'static ViewportAdjuster>,
+        ),
+    >,
+    render_targets: RenderTargets<'w, 's>,
+    changed_events: EventWriter<'w, ViewportChanged>,
+    diagnostics: Diagnostics<'w, 's>,
+    logged: Local<'s, SyncLog>,
+    commands: Commands<'w, 's>,
+}
+
+impl ViewportSyncer<'_, '_> {
+    /// Fits the given cameras, along with the rest of their groups, and records the diagnostics.
+    fn sync(&mut self, mut entities: EntityHashSet, mut dropped_events: u32) {
+        let ViewportSyncer {
+            camera_query,
+            render_targets,
+            changed_events,
+            diagnostics,
+            logged,
+            commands,
+        } = self;
+        // count the syncs, for the diagnostics
+        let mut syncs = 0;
+
+        // the rest of a group is synced along with any camera in it, except manual cameras,
+        // which keep their viewport until they're changed themselves
+        let groups: Vec<u32> = entities
+            .iter()
+            .filter_map(|entity| match camera_query.get(*entity) {
+                Ok((.., Some(group), _)) => Some(group.0),
+                _ => None,
+            })
+            .collect();
+        entities.extend(
+            camera_query
+                .iter()
+                .filter(|(_, fixed_viewport, ..)| !fixed_viewport.manual)
+                .filter_map(|(entity, .., group, _)| match group {
+                    Some(group) if groups.contains(&group.0) => Some(entity),
+                    _ => None,
+                }),
+        );
+
+        // sync the lowest ordered camera of each group first, as its viewport is shared with the rest
+        let mut entities: Vec<Entity> = entities.into_iter().collect();
+        entities.sort_by_key(|entity| {
+            let order = camera_query
+                .get(*entity)
+                .map_or(0, |(_, _, camera, ..)| camera.order);
+            (order, *entity)
+        });
+        let mut group_viewports: HashMap<(u32, UVec2), (Viewport, Rect)> = HashMap::default();
+
+        for entity in entities {
+            let (
+                _,
+                fixed_viewport,
+                mut camera,
+                projection,
+                uncropped,
+                current_viewport,
+                warned,
+                size_override,
+                group,
+                adjuster,
+            ) = match camera_query.get_mut(entity) {
+                Ok(item) => item,
+                Err(_) => {
+                    dropped_events += 1;
+                    continue;
+                }
+            };
+
+            // an inactive camera keeps whatever viewport it has, and is synced again once it's active
+            if !camera.is_active {
+                continue;
+            }
+
+            // a non-positive aspect ratio would produce a garbage viewport, so keep the last one
+            // warning only once until it's valid again, as every sync would repeat it
+            let invalid = fixed_viewport.enabled && !fixed_viewport.has_valid_aspect_ratio();
+            match invalid {
+                true => {
+                    if logged.invalid_aspect_ratio.insert(entity) {
+                        warn!(
+                            "fixed viewport on {:?} has an invalid aspect ratio of {}, skipping sync",
+                            entity,
+                            fixed_viewport.fixed_aspect_ratio()
+                        );
+                    }
+                    dropped_events += 1;
+                    continue;
+                }
+                false => {
+                    logged.invalid_aspect_ratio.remove(&entity);
+                }
+            }
+
+            // resolve the size of the render target, warning once per camera when it can't be
+            let target_size = match size_override
+                .map(|size_override| size_override.0)
+                .or_else(|| render_targets.size(&camera.target))
+            {
+                Some(target_size) => target_size,
+                None => {
+                    if !warned {
+                        warn!(
+                            "could not resolve the render target {:?} of fixed viewport camera {:?}, skipping sync",
+                            camera.target, entity
+                        );
+                        commands.entity(entity).insert(UnresolvedTargetWarned);
+                    }
+                    dropped_events += 1;
+                    continue;
+                }
+            };
+            if warned {
+                commands.entity(entity).remove::<UnresolvedTargetWarned>();
+            }
+
+            // a canvas stands in for the render target in the fit math
+            let fit_size = fixed_viewport.canvas_size.unwrap_or(target_size);
+            let safe_area_insets = match fixed_viewport.canvas_size {
+                Some(_) => SafeAreaInsets::default(),
+                None => render_targets.safe_area_insets(&camera.target, target_size),
+            };
+            // a group's viewport is only shared between enabled cameras fitted to the same size,
+            // so it never reaches past a smaller target or replaces a fit with a disabled camera's whole target
+            let group_key = group
+                .filter(|_| fixed_viewport.enabled)
+                .map(|group| (group.0, fit_size));
+            let group_viewport = group_key.and_then(|group_key| group_viewports.get(&group_key));
+            let viewport = match (
+                group_viewport,
+                fixed_viewport.enabled,
+                fixed_viewport.use_logical_size,
+            ) {
+                // the rest of a group take the viewport its first camera was fitted to
+                (Some(group_viewport), ..) => Some(group_viewport.clone()),
+                // a disabled fixed viewport leaves the camera rendering to the whole target
+                (None, false, _) => {
+                    full_viewport(target_size).map(|viewport| (viewport, crop::FULL_CROP))
+                }
+                (None, true, true) => {
+                    // fit in logical pixels, then scale the result back up to the physical target
+                    let scale_factor = render_targets.scale_factor(&camera.target);
+                    let logical_size = (fit_size.as_vec2() / scale_factor).round().as_uvec2();
+                    let logical_insets = safe_area_insets.to_logical(scale_factor);
+                    fit_in_safe_area(logical_size, &logical_insets, fixed_viewport).map(
+                        |(viewport, crop)| {
+                            (
+                                to_physical_viewport(&viewport, scale_factor, fit_size),
+                                crop,
+                            )
+                        },
+                    )
+                }
+                (None, true, false) => {
+                    fit_in_safe_area(fit_size, &safe_area_insets, fixed_viewport)
+                }
+            };
+            // the rest of a group take the viewport as the first camera adjusted it
+            let viewport = match (group_viewport, adjuster) {
+                (None, Some(adjuster)) if fixed_viewport.enabled => {
+                    viewport.map(|(viewport, crop)| (adjuster.adjust(viewport, fit_size), crop))
+                }
+                _ => viewport,
+            };
+            let (viewport, crop) = match viewport {
+                Some(item) => item,
+                None => continue,
+            };
+            if let Some(group_key) = group_key {
+                group_viewports
+                    .entry(group_key)
+                    .or_insert((viewport.clone(), crop));
+            }
+            syncs += 1;
+
+            // note when the minimum size is holding the viewport up, once until it's released,
+            // as it explains a viewport larger than its fit or reaching into the bars
+            let min_size = fixed_viewport.min_size;
+            let clamped = fixed_viewport.enabled
+                && min_size.cmpgt(UVec2::ONE).any()
+                && viewport.physical_size.cmple(min_size).any();
+            match clamped {
+                true => {
+                    if logged.clamped.insert(entity) {
+                        debug!(
+                            "fixed viewport on {:?} is held at its minimum size of {}, within a render target of {}",
+                            entity, min_size, target_size
+                        );
+                    }
+                }
+                false => {
+                    logged.clamped.remove(&entity);
+                }
+            }
 
-        for (fixed_viewport, camera) in cameras.iter_mut() {
-            // get the required data
-            let window_width = window.physical_width() as f32;
-            let window_height = window.physical_height() as f32;
-            let window_ratio = window_width / window_height;
-            let mut viewport_width = window_width;
-            let mut viewport_height = window_height;
-            let mut viewport_x = 0f32;
-            let mut viewport_y = 0f32;
-
-            // determine the best fit for the given aspect ratio
-            if window_ratio > fixed_viewport.aspect_ratio {
-                viewport_width = viewport_height * fixed_viewport.aspect_ratio;
-                viewport_x = window_width / 2. - viewport_width / 2.;
-            } else {
-                viewport_height = viewport_width / fixed_viewport.aspect_ratio;
-                viewport_y = window_height / 2. - viewport_height / 2.;
-            }
-
-            // update the viewport accordingly
-            camera.viewport = Some(Viewport {
-                physical_position: UVec2::new(viewport_x as u32, viewport_y as u32),
-                physical_size: UVec2::new(viewport_width as u32, viewport_height as u32),
-                ..default()
+            // update the viewport accordingly, leaving a canvas viewport for the app to apply
+            let changed = match (fixed_viewport.enabled, fixed_viewport.canvas_size) {
+                (true, Some(_)) => !current_viewport
+                    .as_ref()
+                    .is_some_and(|current_viewport| same_viewport(&current_viewport.0, &viewport)),
+                _ => {
+                    let applied = fixed_viewport.enabled.then(|| viewport.clone());
+                    let changed = match (&camera.viewport, &applied) {
+                        (Some(previous), Some(viewport)) => !same_viewport(previous, viewport),
+                        (None, None) => false,
+                        _ => true,
+                    };
+                    // only assign when different, so the camera's change detection isn't tripped needlessly
+                    if changed {
+                        camera.viewport = applied;
+                    }
+                    changed
+                }
+            };
+            let region = match fixed_viewport.enabled {
+                true => fixed_viewport.safe_region_rect(fit_size, &safe_area_insets),
+                false => URect::from_corners(UVec2::ZERO, target_size),
+            };
+            commands.entity(entity).insert(SyncedViewport {
+                viewport: viewport.clone(),
+                target_size,
+                region,
+                target: camera.target.clone(),
+                scale_factor: render_targets.scale_factor(&camera.target),
             });
+            commands
+                .entity(entity)
+                .insert(ViewportUniform::new(&viewport, target_size));
+            match fixed_viewport.scale_factor(&viewport) {
+                Some(scale) => commands.entity(entity).insert(ViewportScale(scale)),
+                None => commands.entity(entity).remove::<ViewportScale>(),
+            };
+
+            // only touch the current viewport when it actually changes, so its change detection is meaningful
+            match current_viewport {
+                Some(mut current_viewport) => {
+                    if !same_viewport(&current_viewport.0, &viewport) {
+                        current_viewport.0 = viewport.clone();
+                    }
+                }
+                None => {
+                    commands
+                        .entity(entity)
+                        .insert(CurrentViewport(viewport.clone()));
+                }
+            }
+
+            // keep the projection showing the same area of the world if asked to, and showing only the visible part
+            // of a cropped fit, leaving a canvas viewport's crop for the app to apply with it
+            if let Some(mut projection) = projection {
+                let crop = match fixed_viewport.canvas_size {
+                    Some(_) => crop::FULL_CROP,
+                    None => crop,
+                };
+                let viewport_size =
+                    viewport.physical_size.as_vec2() / render_targets.scale_factor(&camera.target);
+                crop::crop_projection(
+                    entity,
+                    &mut projection,
+                    uncropped,
+                    fixed_viewport.projection.scaling_mode(&fixed_viewport.mode),
+                    crop,
+                    viewport_size,
+                    commands,
+                );
+            }
+
+            // let others know the viewport actually moved or resized
+            if changed {
+                commands.trigger_targets(
+                    ViewportSynced {
+                        viewport: viewport.clone(),
+                    },
+                    entity,
+                );
+                changed_events.send(ViewportChanged {
+                    camera: entity,
+                    viewport,
+                });
+            }
+        }
+
+        diagnostics.add_measurement(&FixedViewportPlugin::SYNCS, || syncs as f64);
+        diagnostics.add_measurement(&FixedViewportPlugin::DROPPED_EVENTS, || {
+            dropped_events as f64
+        });
+    }
+}
+
+/// The on demand sync system, registered once by the plugin so it keeps its state between runs
+/// rather than being built anew for each call.
+#[derive(Resource)]
+struct SyncViewportSystem(SystemId<Entity>);
+
+/// Syncs the camera's viewport immediately, for deterministic control such as in scripted sequences.
+///
+/// Only the camera and the rest of its group are synced. This requires the [`FixedViewportPlugin`].
+pub fn sync_camera_viewport(world: &mut World, camera: Entity) {
+    let sync_system = world.resource::<SyncViewportSystem>().0;
+    if let Err(error) = world.run_system_with_input(sync_system, camera) {
+        warn!(
+            "could not sync the viewport of camera {:?}: {}",
+            camera, error
+        );
+    }
+}
+
+/// Syncs a camera's viewport as soon as the command is applied, with [`sync_camera_viewport`].
+pub struct SyncCameraViewport(pub Entity);
+
+impl Command for SyncCameraViewport {
+    fn apply(self, world: &mut World) {
+        sync_camera_viewport(world, self.0);
+    }
+}
+
+/// A viewport covering the whole render target, or `None` if the target has no area.
+fn full_viewport(target_size: UVec2) -> Option<Viewport> {
+    match target_size.x == 0 || target_size.y == 0 {
+        true => None,
+        false => Some(Viewport {
+            physical_position: UVec2::ZERO,
+            physical_size: target_size,
+            ..default()
+        }),
+    }
+}
+
+/// Scales a viewport fitted in logical pixels to physical pixels, keeping it within the physical target.
+fn to_physical_viewport(viewport: &Viewport, scale_factor: f32, target_size: UVec2) -> Viewport {
+    let physical_position = (viewport.physical_position.as_vec2() * scale_factor)
+        .round()
+        .as_uvec2()
+        .min(target_size);
+    let physical_size = (viewport.physical_size.as_vec2() * scale_factor)
+        .round()
+        .as_uvec2()
+        .min(target_size - physical_position);
+    Viewport {
+        physical_position,
+        physical_size,
+        depth: viewport.depth.clone(),
+    }
+}
+
+/// The normalized rect a viewport covers within a render target of the given physical size,
+/// for sampling just the viewport when compositing a render target image.
+pub fn viewport_uv_rect(viewport: &Viewport, target_size: UVec2) -> Rect {
+    let target_size = target_size.max(UVec2::ONE).as_vec2();
+    let min = viewport.physical_position.as_vec2() / target_size;
+    let max = min + viewport.physical_size.as_vec2() / target_size;
+    Rect { min, max }
+}
+
+/// Computes the viewport a fixed viewport fits to within a render target of the given physical size.
+///
+/// Returns `None` when the render target has no area to fit into, such as a minimized window,
+/// or when the fixed viewport doesn't have a valid aspect ratio.
+///
+/// This is [`compute_fixed_viewport_in_safe_area`] without any insets.
+pub fn compute_fixed_viewport(
+    target_size: UVec2,
+    fixed_viewport: &FixedViewport,
+) -> Option<Viewport> {
+    compute_fixed_viewport_in_safe_area(target_size, &SafeAreaInsets::default(), fixed_viewport)
+}
+
+/// Computes the viewport of the given aspect ratio and scale mode within a render target of the given physical size,
+/// with every other setting left at its default.
+///
+/// This has no ECS dependencies, so the same layout can be predicted anywhere the target size is known.
+/// It's a shorthand for [`compute_fixed_viewport`] with a default fixed viewport.
+pub fn fit_viewport(target_size: UVec2, aspect_ratio: f32, mode: ScaleMode) -> Option<Viewport> {
+    compute_fixed_viewport(
+        target_size,
+        &FixedViewport {
+            mode,
+            ..FixedViewport::new(aspect_ratio)
+        },
+    )
+}
+
+/// Computes the viewport a fixed viewport fits to within the safe area of a render target of the given physical size.
+///
+/// The fixed viewport's region and margin are applied within the area left inside the insets.
+///
+/// This is the canonical fit, the one syncs use, which [`compute_fixed_viewport`] and [`fit_viewport`] wrap.
+pub fn compute_fixed_viewport_in_safe_area(
+    target_size: UVec2,
+    safe_area_insets: &SafeAreaInsets,
+    fixed_viewport: &FixedViewport,
+) -> Option<Viewport> {
+    fit_in_safe_area(target_size, safe_area_insets, fixed_viewport).map(|(viewport, _)| viewport)
+}
+
+/// Fits a fixed viewport within the safe area like [`compute_fixed_viewport_in_safe_area`], along with the part of
+/// the fitted image left visible once a filled viewport is cropped to the available space.
+fn fit_in_safe_area(
+    target_size: UVec2,
+    safe_area_insets: &SafeAreaInsets,
+    fixed_viewport: &FixedViewport,
+) -> Option<(Viewport, Rect)> {
+    // a minimized window has no area to fit into
+    if target_size.x == 0 || target_size.y == 0 || !fixed_viewport.has_valid_aspect_ratio() {
+        return None;
+    }
+
+    // shrink the camera's region of the safe area by the margin, without going negative
+    let region = fixed_viewport.safe_region_rect(target_size, safe_area_insets);
+    let margin = &fixed_viewport.margin;
+    let available_position = region.min + UVec2::new(margin.left, margin.top).min(region.size());
+    let available_size = region
+        .size()
+        .saturating_sub(UVec2::new(margin.left, margin.top))
+        .saturating_sub(UVec2::new(margin.right, margin.bottom));
+
+    // get the required data
+    let available_width = available_size.x as f32;
+    let available_height = available_size.y as f32;
+    let available_ratio = available_width / available_height;
+    let mut viewport_width = available_width;
+    let mut viewport_height = available_height;
+    // the aspect ratios are of the displayed image, so convert them to and from physical pixels
+    let pixel_aspect = fixed_viewport.pixel_aspect;
+    let aspect_ratio =
+        fixed_viewport.effective_aspect_ratio(available_ratio * pixel_aspect) / pixel_aspect;
+
+    // determine the best fit for the given aspect ratio, covering the space when filling or when
+    // the bars the fit would need aren't allowed
+    let wide = available_ratio > aspect_ratio;
+    let bars_allowed = match wide {
+        true => fixed_viewport.allow_vertical_bars,
+        false => fixed_viewport.allow_horizontal_bars,
+    };
+    let fill = matches!(fixed_viewport.mode, ScaleMode::Fill) || !bars_allowed;
+    match fixed_viewport.mode {
+        // there is nothing to fit into, so fall back to the minimum size
+        _ if available_size.x == 0 || available_size.y == 0 => {
+            viewport_width = 0.;
+            viewport_height = 0.;
         }
+        // stretching ignores the aspect ratio and uses the whole space
+        ScaleMode::Stretch => {}
+        // fixed extents ignore the aspect ratio and lock the given axes
+        ScaleMode::FixedExtent { width, height } => {
+            if let Some(width) = width {
+                viewport_width = (width as f32).min(available_width);
+            }
+            if let Some(height) = height {
+                viewport_height = (height as f32).min(available_height);
+            }
+        }
+        // ratios close enough to match use the whole space, so resize jitter doesn't flicker a thin bar
+        _ if (available_ratio - aspect_ratio).abs() <= fixed_viewport.aspect_tolerance => {}
+        _ if wide != fill => {
+            viewport_width = viewport_height * aspect_ratio;
+        }
+        _ => viewport_height = viewport_width / aspect_ratio,
+    }
+
+    // snap down to an allowed scale of the base resolution
+    if let Some(base_resolution) = fixed_viewport.mode.base_resolution() {
+        let base_width = base_resolution.x.max(1) as f32;
+        let base_height = base_resolution.y.max(1) as f32;
+        let fit_scale = (viewport_width / base_width).min(viewport_height / base_height);
+        let fit_scale = match fixed_viewport.max_scale {
+            Some(max_scale) => fit_scale.min(max_scale.max(1) as f32),
+            None => fit_scale,
+        };
+        let scale = match &fixed_viewport.mode {
+            ScaleMode::SnapSteps { steps, .. } => {
+                // take the largest step that fits, or the smallest step and let it clip
+                let fitting = steps
+                    .iter()
+                    .copied()
+                    .filter(|step| *step > 0. && *step <= fit_scale)
+                    .reduce(f32::max);
+                match fitting {
+                    Some(step) => step,
+                    None => steps
+                        .iter()
+                        .copied()
+                        .filter(|step| *step > 0.)
+                        .reduce(f32::min)
+                        .unwrap_or(1.),
+                }
+            }
+            _ => fit_scale.floor().max(1.),
+        };
+        // never draw the base resolution larger than 1:1, but still shrink it to fit small windows
+        let scale = match fixed_viewport.no_upscale {
+            true => scale.min(fit_scale).min(1.),
+            false => scale,
+        };
+        viewport_width = base_width * scale;
+        viewport_height = base_height * scale;
+    }
+
+    // round to whole pixels before placing, so the leftover space is split predictably,
+    // never rounding a viewport that fits past the available space
+    let rounding = fixed_viewport.pixel_rounding;
+    viewport_width = rounding
+        .apply(viewport_width)
+        .min(available_width.max(viewport_width.ceil()));
+    viewport_height = rounding
+        .apply(viewport_height)
+        .min(available_height.max(viewport_height.ceil()));
+
+    // round down to whole grid cells, so tiles are never cut at the edges
+    if let Some(cell) = fixed_viewport.grid_align.filter(|cell| *cell > 0) {
+        let cell = cell as f32;
+        viewport_width = ((viewport_width / cell).floor() * cell).max(cell);
+        viewport_height = ((viewport_height / cell).floor() * cell).max(cell);
+    }
+
+    // place the viewport within the leftover space, giving any odd pixel to the far bar
+    let anchor = fixed_viewport.alignment.anchor();
+    let mut viewport_x = ((available_width - viewport_width) * anchor.x).floor();
+    let mut viewport_y = ((available_height - viewport_height) * anchor.y).floor();
+
+    // crop the overflow of a filled viewport to the available space, noting the part of the fitted image left visible,
+    // as well as a snapped scale too large for the space, so it's clipped at its scale rather than squeezed
+    let overflows = viewport_width > available_width || viewport_height > available_height;
+    let mut crop = crop::FULL_CROP;
+    if fill || overflows {
+        if viewport_width > 0. && viewport_height > 0. {
+            let overflow = Vec2::new(-viewport_x.min(0.), -viewport_y.min(0.));
+            let size = Vec2::new(viewport_width, viewport_height);
+            let visible = Vec2::new(available_width, available_height).min(size);
+            crop = Rect::from_corners(overflow / size, (overflow + visible) / size);
+        }
+        viewport_width = viewport_width.min(available_width);
+        viewport_height = viewport_height.min(available_height);
+        viewport_x = viewport_x.max(0.);
+        viewport_y = viewport_y.max(0.);
+    }
+
+    // shrink the viewport towards its center, leaving an even border inside the fit
+    let fill_fraction = fixed_viewport.fill_fraction.clamp(0., 1.);
+    if fill_fraction < 1. {
+        let shrunk_width = rounding.apply(viewport_width * fill_fraction);
+        let shrunk_height = rounding.apply(viewport_height * fill_fraction);
+        viewport_x += ((viewport_width - shrunk_width) / 2.).floor();
+        viewport_y += ((viewport_height - shrunk_height) / 2.).floor();
+        viewport_width = shrunk_width;
+        viewport_height = shrunk_height;
+    }
+
+    // the viewport now fits the available space, and only the minimum size can hold it past that,
+    // so keep it within the render target, shrinking and then shifting it back inside,
+    // as a viewport reaching past the target is an error when rendering
+    let physical_size = UVec2::new(viewport_width as u32, viewport_height as u32)
+        .max(fixed_viewport.min_size)
+        .min(target_size);
+    let physical_position = (available_position + UVec2::new(viewport_x as u32, viewport_y as u32))
+        .min(target_size - physical_size);
+
+    Some((
+        Viewport {
+            physical_position,
+            physical_size,
+            ..default()
+        },
+        crop,
+    ))
+}
+
+/// The cameras and windows already logged about by [`sync_viewport`], so each is only logged once.
+#[derive(Default)]
+struct SyncLog {
+    /// Cameras skipped for an invalid aspect ratio, until it's valid again.
+    invalid_aspect_ratio: EntityHashSet,
+    /// Cameras held at their minimum size, until they're fitted above it again.
+    clamped: EntityHashSet,
+    /// Windows that sync events were dropped for, as they no longer exist.
+    missing_windows: EntityHashSet,
+}
+
+/// Resets the camera's viewport to the full render target when its fixed viewport is removed.
+#[allow(clippy::type_complexity)]
+fn reset_viewport(
+    mut removed: RemovedComponents<FixedViewport>,
+    mut camera_query: Query<(
+        &mut Camera,
+        &SyncedViewport,
+        Option<&mut OrthographicProjection>,
+        Option<&crop::UncroppedProjection>,
+    )>,
+    mut commands: Commands,
+) {
+    for entity in removed.read() {
+        let (mut camera, synced_viewport, projection, uncropped) =
+            match camera_query.get_mut(entity) {
+                Ok(item) => item,
+                Err(_) => continue,
+            };
+        reset_synced_camera(
+            entity,
+            &mut camera,
+            synced_viewport,
+            projection,
+            uncropped,
+            &mut commands,
+        );
+    }
+}
+
+/// Resets the viewport of cameras whose target window was despawned, so the stale viewport doesn't linger.
+#[allow(clippy::type_complexity)]
+fn reset_despawned_window_viewport(
+    mut removed: RemovedComponents<Window>,
+    mut camera_query: Query<(
+        Entity,
+        &mut Camera,
+        &SyncedViewport,
+        Option<&mut OrthographicProjection>,
+        Option<&crop::UncroppedProjection>,
+    )>,
+    mut commands: Commands,
+) {
+    for window_entity in removed.read() {
+        for (entity, mut camera, synced_viewport, projection, uncropped) in camera_query.iter_mut()
+        {
+            match synced_viewport.target {
+                RenderTarget::Window(WindowRef::Entity(target)) if target == window_entity => {}
+                _ => continue,
+            }
+            warn!(
+                "target window {:?} of fixed viewport camera {:?} was despawned, resetting its viewport",
+                window_entity, entity
+            );
+            reset_synced_camera(
+                entity,
+                &mut camera,
+                synced_viewport,
+                projection,
+                uncropped,
+                &mut commands,
+            );
+        }
+    }
+}
+
+/// Takes the synced viewport off a camera and removes everything the syncs added to it.
+fn reset_synced_camera(
+    entity: Entity,
+    camera: &mut Mut<Camera>,
+    synced_viewport: &SyncedViewport,
+    projection: Option<Mut<OrthographicProjection>>,
+    uncropped: Option<&crop::UncroppedProjection>,
+    commands: &mut Commands,
+) {
+    // leave the viewport alone if other code has replaced it since the last sync
+    if let Some(viewport) = &camera.viewport {
+        if same_viewport(viewport, &synced_viewport.viewport) {
+            camera.viewport = None;
+        }
+    }
+    // the whole image is shown again, so the projection no longer needs cropping
+    if let (Some(mut projection), Some(uncropped)) = (projection, uncropped) {
+        uncropped.restore(&mut projection);
+    }
+    commands.entity(entity).remove::<(
+        SyncedViewport,
+        CurrentViewport,
+        ViewportUniform,
+        ViewportScale,
+        UnresolvedTargetWarned,
+        crop::UncroppedProjection,
+    )>();
+}
+
+/// Whether two render targets are the same target.
+pub(crate) fn same_target(a: &RenderTarget, b: &RenderTarget) -> bool {
+    match (a, b) {
+        (RenderTarget::Window(a), RenderTarget::Window(b)) => match (a, b) {
+            (WindowRef::Primary, WindowRef::Primary) => true,
+            (WindowRef::Entity(a), WindowRef::Entity(b)) => a == b,
+            _ => false,
+        },
+        (RenderTarget::Image(a), RenderTarget::Image(b)) => a == b,
+        (RenderTarget::TextureView(a), RenderTarget::TextureView(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Whether two viewports cover the same physical area.
+fn same_viewport(a: &Viewport, b: &Viewport) -> bool {
+    a.physical_position == b.physical_position && a.physical_size == b.physical_size
+}
+
+/// Whether two scaling modes are the same, as [`ScalingMode`] doesn't implement [`PartialEq`].
+pub(crate) fn same_scaling_mode(a: &ScalingMode, b: &ScalingMode) -> bool {
+    match (a, b) {
+        (ScalingMode::WindowSize(a), ScalingMode::WindowSize(b)) => a == b,
+        (
+            ScalingMode::AutoMin {
+                min_width: a_width,
+                min_height: a_height,
+            },
+            ScalingMode::AutoMin {
+                min_width: b_width,
+                min_height: b_height,
+            },
+        )
+        | (
+            ScalingMode::AutoMax {
+                max_width: a_width,
+                max_height: a_height,
+            },
+            ScalingMode::AutoMax {
+                max_width: b_width,
+                max_height: b_height,
+            },
+        )
+        | (
+            ScalingMode::Fixed {
+                width: a_width,
+                height: a_height,
+            },
+            ScalingMode::Fixed {
+                width: b_width,
+                height: b_height,
+            },
+        ) => a_width == b_width && a_height == b_height,
+        (ScalingMode::FixedVertical(a), ScalingMode::FixedVertical(b))
+        | (ScalingMode::FixedHorizontal(a), ScalingMode::FixedHorizontal(b)) => a == b,
+        _ => false,
     }
 }
@@ -0,0 +1,96 @@
+use bevy::{
+    ecs::entity::EntityHashMap,
+    prelude::*,
+    render::camera::RenderTarget,
+    window::{PrimaryWindow, WindowRef, WindowResized},
+};
+
+use crate::{primary_window_entity, FixedViewport};
+
+/// How long a window has to go without resizing before it's snapped, so it isn't fought over while being dragged.
+const RESIZE_SETTLE_SECONDS: f32 = 0.25;
+
+/// Attach this to a camera with a fixed viewport rendering to a window to resize the window to match its aspect ratio
+/// once the user stops resizing it, leaving no letterbox bars.
+///
+/// The window is shrunk to the size of the fitted viewport, so it never grows past where the user left it.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AspectRatioLocked {
+    /// How far the window's aspect ratio may be from the fixed aspect ratio before it's snapped.
+    pub tolerance: f32,
+}
+
+impl Default for AspectRatioLocked {
+    fn default() -> Self {
+        Self { tolerance: 0.01 }
+    }
+}
+
+/// Snaps windows to the aspect ratio of their locked cameras once they've settled after being resized.
+pub(crate) fn snap_locked_windows(
+    mut resize_events: EventReader<WindowResized>,
+    camera_query: Query<(&Camera, &FixedViewport, &AspectRatioLocked)>,
+    mut window_query: Query<(Entity, &mut Window)>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
+    mut resized_at: Local<EntityHashMap<f32>>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed_seconds();
+    for event in resize_events.read() {
+        resized_at.insert(event.window, now);
+    }
+
+    // only snap the windows that have stopped resizing
+    let settled = resized_at
+        .iter()
+        .filter(|(_, resized_at)| now - **resized_at >= RESIZE_SETTLE_SECONDS)
+        .map(|(window_entity, _)| *window_entity)
+        .collect::<Vec<_>>();
+    for window_entity in settled {
+        resized_at.remove(&window_entity);
+        // the window stands in for the primary window as the sync resolves it
+        let primary = primary_window_entity(
+            primary_window_query.iter(),
+            window_query.iter().map(|(window_entity, _)| window_entity),
+        ) == Some(window_entity);
+        let (_, mut window) = match window_query.get_mut(window_entity) {
+            Ok(item) => item,
+            Err(_) => continue,
+        };
+
+        for (camera, fixed_viewport, locked) in camera_query.iter() {
+            let targets_window = match camera.target {
+                RenderTarget::Window(WindowRef::Primary) => primary,
+                RenderTarget::Window(WindowRef::Entity(entity)) => entity == window_entity,
+                _ => false,
+            };
+            // an invalid aspect ratio is skipped by the sync too, so there's nothing to snap to
+            if !targets_window
+                || !fixed_viewport.enabled
+                || !fixed_viewport.has_valid_aspect_ratio()
+            {
+                continue;
+            }
+
+            // shrink whichever side leaves bars, in logical pixels as the window is resized in them,
+            // converting the aspect ratio of the displayed image to pixels as the fit does
+            let size = Vec2::new(window.width(), window.height());
+            let window_ratio = size.x / size.y;
+            let pixel_aspect = fixed_viewport.pixel_aspect;
+            let aspect_ratio =
+                fixed_viewport.effective_aspect_ratio(window_ratio * pixel_aspect) / pixel_aspect;
+            if !size.cmpgt(Vec2::ZERO).all()
+                || (window_ratio - aspect_ratio).abs() <= locked.tolerance
+            {
+                continue;
+            }
+            let snapped = match window_ratio > aspect_ratio {
+                true => Vec2::new(size.y * aspect_ratio, size.y),
+                false => Vec2::new(size.x, size.x / aspect_ratio),
+            };
+            window.resolution.set(snapped.x.round(), snapped.y.round());
+            break;
+        }
+    }
+}
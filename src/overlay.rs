@@ -0,0 +1,252 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    prelude::*,
+    render::{
+        camera::ScalingMode,
+        view::{Layer, RenderLayers},
+    },
+};
+
+use crate::{
+    same_target, BarClearColor, DebugViewportGizmo, LetterboxColor, SyncedViewport, ViewportDim,
+};
+
+/// The render layer the plugin's overlays, such as letterbox bars, are drawn on.
+///
+/// This should be kept free of other content.
+pub const OVERLAY_RENDER_LAYER: Layer = 31;
+
+/// The distance in world units between the origins of overlay cameras, along the x axis.
+///
+/// Each overlay camera only sees the area around its own origin, so overlays sharing the render layer,
+/// including the debug gizmos, never show up on another camera's overlay. This is well past the largest render target.
+const OVERLAY_SPACING: f32 = 16384.;
+
+/// Tracks the managed camera that draws every overlay of a camera, such as its letterbox bars.
+#[derive(Component)]
+pub(crate) struct Overlay {
+    pub(crate) camera: Entity,
+    /// The world position the overlay camera is centered on.
+    pub(crate) origin: Vec2,
+}
+
+/// Marks a managed camera that draws the overlays of its owner.
+#[derive(Component)]
+pub(crate) struct OverlayCamera {
+    owner: Entity,
+}
+
+/// Marks an entity managed on behalf of an owner camera, despawned once the owner loses the component `T`.
+#[derive(Component)]
+pub(crate) struct ManagedBy<T: Component> {
+    owner: Entity,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Component> ManagedBy<T> {
+    pub(crate) fn new(owner: Entity) -> Self {
+        Self {
+            owner,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Despawns the entities managed for cameras that no longer have the component `T`, or no longer exist,
+/// and removes the component `M` tracking them from the owner.
+pub(crate) fn despawn_managed<T: Component, M: Component>(
+    mut removed: RemovedComponents<T>,
+    managed_query: Query<(Entity, &ManagedBy<T>)>,
+    mut commands: Commands,
+) {
+    for entity in removed.read() {
+        for (managed, managed_by) in managed_query.iter() {
+            if managed_by.owner == entity {
+                commands.entity(managed).despawn_recursive();
+            }
+        }
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<M>();
+        }
+    }
+}
+
+/// Spawns the managed overlay camera for cameras that have just been given an overlay.
+#[allow(clippy::type_complexity)]
+pub(crate) fn spawn_overlay_cameras(
+    camera_query: Query<
+        Entity,
+        (
+            Or<(
+                With<LetterboxColor>,
+                With<ViewportDim>,
+                With<DebugViewportGizmo>,
+            )>,
+            Without<Overlay>,
+        ),
+    >,
+    overlay_query: Query<&Overlay>,
+    mut commands: Commands,
+) {
+    // reuse the lowest free slot, so origins stay close to the world origin where floats are most precise
+    let mut used: Vec<f32> = overlay_query
+        .iter()
+        .map(|overlay| overlay.origin.x)
+        .collect();
+    for entity in camera_query.iter() {
+        let origin = (0..)
+            .map(|slot| Vec2::new(slot as f32 * OVERLAY_SPACING, 0.))
+            .find(|origin| !used.contains(&origin.x))
+            .unwrap_or_default();
+        used.push(origin.x);
+
+        // the camera stays inactive until it's first synced with its owner, so it never ties with another camera
+        let mut bundle = Camera2dBundle {
+            camera: Camera {
+                clear_color: ClearColorConfig::None,
+                is_active: false,
+                ..default()
+            },
+            ..default()
+        };
+        bundle.transform.translation.x = origin.x;
+        bundle.transform.translation.y = origin.y;
+        let camera = commands
+            .spawn((
+                bundle,
+                VisibilityBundle::default(),
+                RenderLayers::layer(OVERLAY_RENDER_LAYER),
+                OverlayCamera { owner: entity },
+            ))
+            .id();
+        commands.entity(entity).insert(Overlay { camera, origin });
+    }
+}
+
+/// Keeps each overlay camera drawing over its owner's render target, in physical pixels of the target,
+/// ordered after every other camera on the target so its order never ties with another.
+///
+/// An owner without a synced viewport, such as one whose fixed viewport was removed, has nothing to draw
+/// around, so its overlay camera is turned off until it's synced again.
+#[allow(clippy::type_complexity)]
+pub(crate) fn sync_overlay_cameras(
+    owner_query: Query<(&Camera, &SyncedViewport), Without<OverlayCamera>>,
+    other_query: Query<&Camera, (Without<OverlayCamera>, Without<ManagedBy<BarClearColor>>)>,
+    mut overlay_query: Query<(
+        Entity,
+        &OverlayCamera,
+        &mut Camera,
+        &mut OrthographicProjection,
+    )>,
+) {
+    // stack the overlays of cameras sharing a target in the order of their owners
+    let mut overlays: Vec<(Entity, Entity, &Camera, &SyncedViewport)> = overlay_query
+        .iter()
+        .filter_map(|(entity, overlay_camera, ..)| {
+            owner_query
+                .get(overlay_camera.owner)
+                .ok()
+                .map(|(owner_camera, synced_viewport)| {
+                    (entity, overlay_camera.owner, owner_camera, synced_viewport)
+                })
+        })
+        .collect();
+    overlays.sort_by_key(|(_, owner, owner_camera, _)| (owner_camera.order, *owner));
+
+    // the last overlays drawn for an owner that's no longer synced would linger over its whole target
+    let unsynced: Vec<Entity> = overlay_query
+        .iter()
+        .filter(|(_, overlay_camera, ..)| !owner_query.contains(overlay_camera.owner))
+        .map(|(entity, ..)| entity)
+        .collect();
+
+    let orders: Vec<(Entity, isize)> = overlays
+        .iter()
+        .enumerate()
+        .map(|(index, (entity, _, owner_camera, _))| {
+            let target = &owner_camera.target;
+            let top = other_query
+                .iter()
+                .filter(|camera| same_target(&camera.target, target))
+                .map(|camera| camera.order)
+                .max()
+                .unwrap_or(owner_camera.order);
+            let below = overlays[..index]
+                .iter()
+                .filter(|(_, _, other, _)| same_target(&other.target, target))
+                .count();
+            (
+                *entity,
+                top.saturating_add(1).saturating_add(below as isize),
+            )
+        })
+        .collect();
+
+    for entity in unsynced {
+        if let Ok((_, _, mut camera, _)) = overlay_query.get_mut(entity) {
+            if camera.is_active {
+                camera.is_active = false;
+            }
+        }
+    }
+
+    for ((entity, _, owner_camera, synced_viewport), (_, order)) in overlays.iter().zip(orders) {
+        let (_, _, mut camera, mut projection) = match overlay_query.get_mut(*entity) {
+            Ok(item) => item,
+            Err(_) => continue,
+        };
+
+        // only touch what differs, so the overlay camera's change detection stays meaningful
+        if !same_target(&camera.target, &owner_camera.target) {
+            camera.target = owner_camera.target.clone();
+        }
+        if camera.order != order {
+            camera.order = order;
+        }
+        if camera.is_active != owner_camera.is_active {
+            camera.is_active = owner_camera.is_active;
+        }
+        let target_size = synced_viewport.target_size.as_vec2();
+        let scaled = matches!(
+            projection.scaling_mode,
+            ScalingMode::Fixed { width, height } if width == target_size.x && height == target_size.y
+        );
+        if !scaled {
+            projection.scaling_mode = ScalingMode::Fixed {
+                width: target_size.x,
+                height: target_size.y,
+            };
+        }
+    }
+}
+
+/// Despawns the managed overlay camera of cameras that no longer have any overlay, or no longer exist.
+#[allow(clippy::type_complexity)]
+pub(crate) fn despawn_overlay_cameras(
+    camera_query: Query<(Entity, &OverlayCamera)>,
+    owner_query: Query<
+        (),
+        Or<(
+            With<LetterboxColor>,
+            With<ViewportDim>,
+            With<DebugViewportGizmo>,
+        )>,
+    >,
+    mut commands: Commands,
+) {
+    for (camera, overlay_camera) in camera_query.iter() {
+        if owner_query.contains(overlay_camera.owner) {
+            continue;
+        }
+        commands.entity(camera).despawn_recursive();
+        if let Some(mut entity_commands) = commands.get_entity(overlay_camera.owner) {
+            entity_commands.remove::<Overlay>();
+        }
+    }
+}
+
+/// Converts a point from the render target's top-left origin to an overlay camera's centered, y-up space.
+pub(crate) fn to_overlay_space(point: Vec2, target_size: Vec2) -> Vec2 {
+    Vec2::new(point.x - target_size.x / 2., target_size.y / 2. - point.y)
+}
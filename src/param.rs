@@ -0,0 +1,46 @@
+use bevy::{ecs::system::SystemParam, prelude::*, render::camera::Viewport};
+
+use crate::{CurrentViewport, FixedViewport};
+
+/// Looks up the viewports of cameras with a fixed viewport, for mapping cursor positions in one line.
+#[derive(SystemParam)]
+pub struct FixedViewports<'w, 's> {
+    camera_query: Query<
+        'w,
+        's,
+        (
+            &'static Camera,
+            &'static GlobalTransform,
+            &'static CurrentViewport,
+        ),
+        With<FixedViewport>,
+    >,
+}
+
+impl FixedViewports<'_, '_> {
+    /// The viewport currently applied to the camera, if it has a synced fixed viewport.
+    pub fn viewport_for(&self, camera: Entity) -> Option<&Viewport> {
+        self.camera_query
+            .get(camera)
+            .ok()
+            .map(|(_, _, current_viewport)| &current_viewport.0)
+    }
+
+    /// Converts a logical window cursor position into a logical position relative to the camera's viewport.
+    ///
+    /// Returns `None` when the position is over a letterbox bar or the camera has no synced fixed viewport.
+    pub fn window_to_viewport(&self, camera: Entity, window_cursor: Vec2) -> Option<Vec2> {
+        let (camera, _, current_viewport) = self.camera_query.get(camera).ok()?;
+        let scale_factor = camera.target_scaling_factor()?;
+        FixedViewport::window_to_viewport(window_cursor * scale_factor, &current_viewport.0)
+            .map(|position| position / scale_factor)
+    }
+
+    /// Converts a logical window cursor position into a 2D world position seen through the camera.
+    ///
+    /// Returns `None` when the position is over a letterbox bar or the camera has no synced fixed viewport.
+    pub fn cursor_to_world(&self, camera: Entity, window_cursor: Vec2) -> Option<Vec2> {
+        let (camera, camera_transform, _) = self.camera_query.get(camera).ok()?;
+        FixedViewport::viewport_cursor_to_world(camera, camera_transform, window_cursor)
+    }
+}
@@ -0,0 +1,50 @@
+use bevy::{ecs::entity::EntityHashMap, prelude::*, render::camera::Viewport};
+
+use crate::CurrentViewport;
+
+/// Every camera with a synced fixed viewport and its current viewport, kept up to date by the plugin,
+/// for debug UIs that want a global view without a query.
+#[derive(Resource, Default, Debug)]
+pub struct FixedViewportRegistry {
+    viewports: EntityHashMap<Viewport>,
+}
+
+impl FixedViewportRegistry {
+    /// The viewport currently applied to the camera, if it has a synced fixed viewport.
+    pub fn get(&self, camera: Entity) -> Option<&Viewport> {
+        self.viewports.get(&camera)
+    }
+
+    /// Every camera with a synced fixed viewport and its current viewport.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &Viewport)> {
+        self.viewports
+            .iter()
+            .map(|(camera, viewport)| (*camera, viewport))
+    }
+
+    /// The number of cameras with a synced fixed viewport.
+    pub fn len(&self) -> usize {
+        self.viewports.len()
+    }
+
+    /// Whether no cameras have a synced fixed viewport.
+    pub fn is_empty(&self) -> bool {
+        self.viewports.is_empty()
+    }
+}
+
+/// Keeps the registry in step with the current viewports of cameras as they're synced and removed.
+pub(crate) fn update_registry(
+    camera_query: Query<(Entity, &CurrentViewport), Changed<CurrentViewport>>,
+    mut removed: RemovedComponents<CurrentViewport>,
+    mut registry: ResMut<FixedViewportRegistry>,
+) {
+    for entity in removed.read() {
+        registry.viewports.remove(&entity);
+    }
+    for (entity, current_viewport) in camera_query.iter() {
+        registry
+            .viewports
+            .insert(entity, current_viewport.0.clone());
+    }
+}
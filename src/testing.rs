@@ -0,0 +1,20 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowResolution},
+};
+
+/// Spawns a fake window of the given physical size into the app, tagged as the primary window if asked,
+/// so fixed viewports can be tested without a windowing backend.
+///
+/// Nothing opens the window, so its size only changes when set directly, such as through
+/// [`WindowResolution::set_physical_resolution`].
+pub fn spawn_test_window(app: &mut App, size: UVec2, primary: bool) -> Entity {
+    let mut window = app.world_mut().spawn(Window {
+        resolution: WindowResolution::new(size.x as f32, size.y as f32),
+        ..default()
+    });
+    if primary {
+        window.insert(PrimaryWindow);
+    }
+    window.id()
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use crate::{AspectRatio, FixedViewport};
+
+/// Attach this to a camera with a fixed viewport to smoothly animate its aspect ratio towards a target.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct FixedViewportTween {
+    /// The aspect ratio to animate towards.
+    pub target: f32,
+    /// How much the aspect ratio changes per second.
+    pub speed: f32,
+}
+
+/// Moves the aspect ratio of tweened fixed viewports towards their targets.
+pub(crate) fn tween_aspect_ratio(
+    mut camera_query: Query<(&mut FixedViewport, &FixedViewportTween)>,
+    time: Res<Time>,
+) {
+    for (mut fixed_viewport, tween) in camera_query.iter_mut() {
+        // only touch the fixed viewport when it needs to move, so it isn't synced needlessly
+        let aspect_ratio = fixed_viewport.fixed_aspect_ratio();
+        let difference = tween.target - aspect_ratio;
+        if difference == 0. {
+            continue;
+        }
+
+        // an animated aspect ratio is no longer exact
+        let step = tween.speed.abs() * time.delta_seconds();
+        fixed_viewport.aspect_ratio = AspectRatio::Value(match difference.abs() <= step {
+            true => tween.target,
+            false => aspect_ratio + step.copysign(difference),
+        });
+    }
+}
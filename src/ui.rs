@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::SyncedViewport;
+
+/// Attach this to a root UI node to keep it exactly covering the viewport of a camera with a fixed viewport.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ViewportTrackingNode {
+    /// The camera whose viewport the node covers.
+    pub camera: Entity,
+}
+
+/// Positions and sizes tracking nodes over their camera's viewport whenever either changes.
+pub(crate) fn track_viewport_nodes(
+    mut node_query: Query<(Ref<ViewportTrackingNode>, &mut Style)>,
+    camera_query: Query<Ref<SyncedViewport>>,
+) {
+    for (tracking_node, mut style) in node_query.iter_mut() {
+        let synced_viewport = match camera_query.get(tracking_node.camera) {
+            Ok(synced_viewport) => synced_viewport,
+            Err(_) => continue,
+        };
+        if !tracking_node.is_changed() && !synced_viewport.is_changed() {
+            continue;
+        }
+
+        // percentages of the target keep the node in place whatever the scale factor or ui scale
+        let target_size = synced_viewport.target_size.as_vec2();
+        let position = synced_viewport.viewport.physical_position.as_vec2() / target_size * 100.;
+        let size = synced_viewport.viewport.physical_size.as_vec2() / target_size * 100.;
+        style.position_type = PositionType::Absolute;
+        style.left = Val::Percent(position.x);
+        style.top = Val::Percent(position.y);
+        style.width = Val::Percent(size.x);
+        style.height = Val::Percent(size.y);
+    }
+}
@@ -0,0 +1,22 @@
+use bevy_fixed_viewport::{FixedAspect, FixedViewport};
+
+#[test]
+fn matches_presets_however_they_are_written() {
+    assert_eq!(FixedViewport::default().aspect(), FixedAspect::R16x9);
+    assert_eq!(FixedViewport::new(16. / 9.).aspect(), FixedAspect::R16x9);
+    assert_eq!(
+        FixedViewport::from_resolution(1920, 1080).aspect(),
+        FixedAspect::R16x9
+    );
+    assert_eq!(
+        FixedViewport::from_resolution(1000, 1000).aspect(),
+        FixedAspect::Custom(1.)
+    );
+}
+
+#[test]
+fn cycles_from_the_default_aspect_ratio() {
+    let mut fixed_viewport = FixedViewport::default();
+    fixed_viewport.set_aspect(fixed_viewport.aspect().next());
+    assert_eq!(fixed_viewport.aspect(), FixedAspect::R16x10);
+}
@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+use bevy_fixed_viewport::{
+    compute_fixed_viewport, fit_viewport, FixedViewport, ScaleMode, ViewportAlignment,
+    ViewportMargin,
+};
+
+#[test]
+fn fits_the_aspect_ratio_within_the_target() {
+    // (target size, aspect ratio, expected position, expected size)
+    let cases = [
+        // wide targets leave bars either side
+        (
+            UVec2::new(1920, 1080),
+            4. / 3.,
+            UVec2::new(240, 0),
+            UVec2::new(1440, 1080),
+        ),
+        (
+            UVec2::new(1000, 500),
+            1.,
+            UVec2::new(250, 0),
+            UVec2::new(500, 500),
+        ),
+        // tall targets leave bars above and below
+        (
+            UVec2::new(1920, 1200),
+            16. / 9.,
+            UVec2::new(0, 60),
+            UVec2::new(1920, 1080),
+        ),
+        (
+            UVec2::new(500, 1000),
+            1.,
+            UVec2::new(0, 250),
+            UVec2::new(500, 500),
+        ),
+        // exact matches cover the whole target
+        (
+            UVec2::new(1280, 720),
+            16. / 9.,
+            UVec2::ZERO,
+            UVec2::new(1280, 720),
+        ),
+        (UVec2::new(800, 800), 1., UVec2::ZERO, UVec2::new(800, 800)),
+    ];
+    for (target_size, aspect_ratio, position, size) in cases {
+        let viewport = fit_viewport(target_size, aspect_ratio, ScaleMode::Fit)
+            .expect("the target should have an area to fit into");
+        assert_eq!(
+            (viewport.physical_position, viewport.physical_size),
+            (position, size),
+            "fitting {} to {}",
+            aspect_ratio,
+            target_size
+        );
+    }
+}
+
+#[test]
+fn splits_odd_leftover_space_with_the_extra_pixel_in_the_far_bar() {
+    let viewport = fit_viewport(UVec2::new(101, 50), 1., ScaleMode::Fit)
+        .expect("the target should have an area to fit into");
+    assert_eq!(viewport.physical_size, UVec2::new(50, 50));
+    // 51 leftover pixels can't split evenly, so the bars are 25 and 26 wide
+    let left = viewport.physical_position.x;
+    let right = 101 - left - viewport.physical_size.x;
+    assert_eq!((left, right), (25, 26));
+}
+
+#[test]
+fn keeps_extreme_placements_within_the_target() {
+    let target_size = UVec2::new(1920, 1080);
+    let alignments = [
+        ViewportAlignment::TopLeft,
+        ViewportAlignment::Center,
+        ViewportAlignment::BottomRight,
+    ];
+    let margins = [
+        ViewportMargin::ZERO,
+        ViewportMargin {
+            left: 1900,
+            top: 1070,
+            ..default()
+        },
+        ViewportMargin {
+            right: 1900,
+            bottom: 1070,
+            ..default()
+        },
+        ViewportMargin::all(u32::MAX),
+    ];
+    let regions = [
+        Rect::new(0., 0., 1., 1.),
+        Rect::new(0.99, 0.99, 1., 1.),
+        Rect::new(-5., -5., 10., 10.),
+    ];
+    let min_sizes = [UVec2::ONE, UVec2::new(640, 360), UVec2::splat(u32::MAX)];
+    let modes = [
+        ScaleMode::Fit,
+        ScaleMode::Fill,
+        ScaleMode::Integer {
+            base_resolution: UVec2::new(4000, 4000),
+        },
+    ];
+
+    for alignment in alignments {
+        for margin in margins {
+            for region in regions {
+                for min_size in min_sizes {
+                    for mode in modes.clone() {
+                        let fixed_viewport = FixedViewport {
+                            alignment,
+                            margin,
+                            region,
+                            min_size,
+                            mode,
+                            ..FixedViewport::new(16. / 9.)
+                        };
+                        let viewport = compute_fixed_viewport(target_size, &fixed_viewport)
+                            .expect("the target should have an area to fit into");
+                        let max = viewport.physical_position + viewport.physical_size;
+                        assert!(
+                            max.cmple(target_size).all(),
+                            "{:?} reaches past the target with {:?}, {:?}, {:?} and {:?}",
+                            viewport,
+                            alignment,
+                            margin,
+                            region,
+                            min_size
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn clips_snapped_scales_to_the_region() {
+    // the smallest step is still too wide for the left half of the window, so it's clipped to the half
+    let fixed_viewport = FixedViewport {
+        mode: ScaleMode::SnapSteps {
+            base_resolution: UVec2::new(1280, 720),
+            steps: vec![1., 2.],
+        },
+        region: Rect::new(0., 0., 0.5, 1.),
+        ..FixedViewport::new(16. / 9.)
+    };
+    let viewport = compute_fixed_viewport(UVec2::new(1920, 1080), &fixed_viewport)
+        .expect("the target should have an area to fit into");
+    assert_eq!(viewport.physical_position, UVec2::new(0, 180));
+    assert_eq!(viewport.physical_size, UVec2::new(960, 720));
+}
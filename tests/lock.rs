@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy, window::WindowResized};
+use bevy_fixed_viewport::{
+    testing::spawn_test_window, AspectRatio, AspectRatioLocked, FixedViewport, FixedViewportPlugin,
+};
+
+/// An app with the plugin, a fake primary window of the given size and a locked camera rendering to it,
+/// with time advancing well past the resize settling time every update.
+fn setup(window_size: UVec2, fixed_viewport: FixedViewport) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+    let window = spawn_test_window(&mut app, window_size, true);
+    app.world_mut().spawn((
+        Camera::default(),
+        fixed_viewport,
+        AspectRatioLocked::default(),
+    ));
+    app.update();
+    (app, window)
+}
+
+/// Reports the window as resized, then lets it settle.
+fn resize(app: &mut App, window: Entity) {
+    let size = app.world().get::<Window>(window).unwrap().size();
+    app.world_mut().send_event(WindowResized {
+        window,
+        width: size.x,
+        height: size.y,
+    });
+    app.update();
+    app.update();
+}
+
+/// The logical size of the window.
+fn window_size(app: &App, window: Entity) -> Vec2 {
+    app.world().get::<Window>(window).unwrap().size()
+}
+
+#[test]
+fn snaps_to_the_aspect_ratio_in_pixels() {
+    // wide pixels halve the width the displayed aspect ratio needs
+    let fixed_viewport = FixedViewport {
+        pixel_aspect: 2.,
+        ..FixedViewport::new(16. / 9.)
+    };
+    let (mut app, window) = setup(UVec2::new(1920, 1080), fixed_viewport);
+    resize(&mut app, window);
+    assert_eq!(window_size(&app, window), Vec2::new(960., 1080.));
+}
+
+#[test]
+fn skips_invalid_aspect_ratios() {
+    let fixed_viewport = FixedViewport {
+        aspect_ratio: AspectRatio::Value(-1.),
+        ..default()
+    };
+    let (mut app, window) = setup(UVec2::new(1920, 1080), fixed_viewport);
+    resize(&mut app, window);
+    assert_eq!(window_size(&app, window), Vec2::new(1920., 1080.));
+}
+
+#[test]
+fn snaps_the_only_window_when_none_is_primary() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(1)));
+    let window = spawn_test_window(&mut app, UVec2::new(1920, 1200), false);
+    app.world_mut().spawn((
+        Camera::default(),
+        FixedViewport::new(16. / 9.),
+        AspectRatioLocked::default(),
+    ));
+    app.update();
+    resize(&mut app, window);
+    assert_eq!(window_size(&app, window), Vec2::new(1920., 1080.));
+}
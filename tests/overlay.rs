@@ -0,0 +1,210 @@
+use bevy::{
+    prelude::*,
+    render::{camera::RenderTarget, view::RenderLayers},
+    window::WindowRef,
+};
+use bevy_fixed_viewport::{
+    testing::spawn_test_window, BarClearColor, FixedViewport, FixedViewportPlugin, LetterboxColor,
+    ViewportDim, OVERLAY_RENDER_LAYER,
+};
+
+/// An app with the plugin and a fake primary window, without any cameras yet.
+fn setup() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default());
+    spawn_test_window(&mut app, UVec2::new(1920, 1200), true);
+    app
+}
+
+/// The orders of the overlay cameras and of every other camera.
+fn camera_orders(app: &mut App) -> (Vec<isize>, Vec<isize>) {
+    let mut query = app.world_mut().query::<(&Camera, Option<&RenderLayers>)>();
+    let (overlays, others): (Vec<_>, Vec<_>) =
+        query.iter(app.world()).partition(|(_, render_layers)| {
+            render_layers.is_some_and(|render_layers| {
+                render_layers.intersects(&RenderLayers::layer(OVERLAY_RENDER_LAYER))
+            })
+        });
+    (
+        overlays.iter().map(|(camera, _)| camera.order).collect(),
+        others.iter().map(|(camera, _)| camera.order).collect(),
+    )
+}
+
+/// The number of overlay cameras still drawing.
+fn active_overlays(app: &mut App) -> usize {
+    let mut query = app.world_mut().query::<(&Camera, &RenderLayers)>();
+    query
+        .iter(app.world())
+        .filter(|(camera, render_layers)| {
+            camera.is_active && render_layers.intersects(&RenderLayers::layer(OVERLAY_RENDER_LAYER))
+        })
+        .count()
+}
+
+#[test]
+fn draws_every_overlay_of_a_camera_with_one_camera_above_the_rest() {
+    let mut app = setup();
+    app.world_mut().spawn((
+        Camera::default(),
+        FixedViewport::new(16. / 9.),
+        LetterboxColor(Color::BLACK),
+        ViewportDim::default(),
+    ));
+    // a minimap drawn over the main camera, one order above it
+    app.world_mut().spawn((
+        Camera {
+            order: 1,
+            ..default()
+        },
+        FixedViewport::new(1.),
+        LetterboxColor(Color::BLACK),
+    ));
+    app.update();
+    app.update();
+
+    let (mut overlays, others) = camera_orders(&mut app);
+    overlays.sort();
+    assert_eq!(overlays, vec![2, 3]);
+    assert_eq!(others.len(), 2);
+}
+
+#[test]
+fn saturates_overlay_orders() {
+    let mut app = setup();
+    app.world_mut().spawn((
+        Camera {
+            order: isize::MAX,
+            ..default()
+        },
+        FixedViewport::new(16. / 9.),
+        LetterboxColor(Color::BLACK),
+    ));
+    app.update();
+    app.update();
+
+    let (overlays, _) = camera_orders(&mut app);
+    assert_eq!(overlays, vec![isize::MAX]);
+}
+
+#[test]
+fn despawns_overlays_along_with_their_components() {
+    let mut app = setup();
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera::default(),
+            FixedViewport::new(16. / 9.),
+            LetterboxColor(Color::BLACK),
+            ViewportDim::default(),
+        ))
+        .id();
+    app.update();
+    app.update();
+    let entities = app.world().entities().len();
+
+    // the overlay camera stays while the dimming still needs it
+    app.world_mut()
+        .entity_mut(camera)
+        .remove::<LetterboxColor>();
+    app.update();
+    assert_eq!(camera_orders(&mut app).0.len(), 1);
+    assert_eq!(app.world().entities().len(), entities - 8);
+
+    app.world_mut().entity_mut(camera).remove::<ViewportDim>();
+    app.update();
+    assert!(camera_orders(&mut app).0.is_empty());
+    assert_eq!(app.world().entities().len(), entities - 13);
+}
+
+#[test]
+fn clears_bars_below_every_camera_on_the_target() {
+    let mut app = setup();
+    app.world_mut()
+        .spawn((Camera::default(), FixedViewport::new(16. / 9.)));
+    app.world_mut().spawn((
+        Camera {
+            order: 1,
+            ..default()
+        },
+        FixedViewport::new(1.),
+        BarClearColor(Color::BLACK),
+    ));
+    app.update();
+
+    let (_, mut others) = camera_orders(&mut app);
+    others.sort();
+    assert_eq!(others, vec![-1, 0, 1]);
+}
+
+#[test]
+fn saturates_bar_clear_orders() {
+    let mut app = setup();
+    app.world_mut().spawn((
+        Camera {
+            order: isize::MIN,
+            ..default()
+        },
+        FixedViewport::new(16. / 9.),
+        BarClearColor(Color::BLACK),
+    ));
+    app.update();
+
+    let (_, others) = camera_orders(&mut app);
+    assert_eq!(others, vec![isize::MIN, isize::MIN]);
+}
+
+#[test]
+fn stops_drawing_letterbox_bars_once_the_fixed_viewport_is_removed() {
+    let mut app = setup();
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera::default(),
+            FixedViewport::new(16. / 9.),
+            LetterboxColor(Color::BLACK),
+        ))
+        .id();
+    app.update();
+    app.update();
+    assert_eq!(active_overlays(&mut app), 1);
+
+    // the camera renders to the whole window again, so the last bars mustn't be left over it
+    app.world_mut().entity_mut(camera).remove::<FixedViewport>();
+    app.update();
+    assert!(app
+        .world()
+        .get::<Camera>(camera)
+        .unwrap()
+        .viewport
+        .is_none());
+    assert_eq!(active_overlays(&mut app), 0);
+
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(FixedViewport::new(16. / 9.));
+    app.update();
+    assert_eq!(active_overlays(&mut app), 1);
+}
+
+#[test]
+fn stops_drawing_the_dimming_once_the_target_window_is_despawned() {
+    let mut app = setup();
+    let window = spawn_test_window(&mut app, UVec2::new(1080, 1080), false);
+    app.world_mut().spawn((
+        Camera {
+            target: RenderTarget::Window(WindowRef::Entity(window)),
+            ..default()
+        },
+        FixedViewport::new(16. / 9.),
+        ViewportDim::default(),
+    ));
+    app.update();
+    app.update();
+    assert_eq!(active_overlays(&mut app), 1);
+
+    app.world_mut().despawn(window);
+    app.update();
+    assert_eq!(active_overlays(&mut app), 0);
+}
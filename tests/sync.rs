@@ -0,0 +1,977 @@
+use bevy::{
+    diagnostic::DiagnosticsStore,
+    prelude::*,
+    render::camera::{CameraProjection, RenderTarget, ScalingMode, Viewport},
+    window::{WindowRef, WindowResized, WindowScaleFactorChanged},
+};
+use bevy_fixed_viewport::{
+    compute_fixed_viewport, sync_camera_viewport, testing::spawn_test_window, AspectRatio,
+    AvailableViewportRect, CurrentViewport, FixedViewport, FixedViewportGroup, FixedViewportPlugin,
+    FixedViewportRegistry, PrimaryWindowFallback, ProjectionLock, SafeAreaInsets, ScaleMode,
+    ViewportAdjuster, ViewportAlignment, ViewportScale,
+};
+
+/// An app with the plugin, a fake primary window of the given physical size and a camera rendering to it.
+fn setup(window_size: UVec2, fixed_viewport: FixedViewport) -> (App, Entity, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default());
+    let window = spawn_test_window(&mut app, window_size, true);
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), fixed_viewport))
+        .id();
+    app.update();
+    (app, window, camera)
+}
+
+/// The viewport currently applied to the camera.
+fn viewport(app: &App, camera: Entity) -> Option<Viewport> {
+    app.world().get::<Camera>(camera).unwrap().viewport.clone()
+}
+
+/// The area of the world the camera's orthographic projection shows within its viewport.
+fn visible_area(app: &App, camera: Entity) -> Rect {
+    let size = viewport(app, camera)
+        .expect("the camera should have a viewport")
+        .physical_size
+        .as_vec2();
+    let mut projection = app
+        .world()
+        .get::<OrthographicProjection>(camera)
+        .unwrap()
+        .clone();
+    projection.update(size.x, size.y);
+    projection.area
+}
+
+/// The number of viewports synced in the last update.
+fn syncs(app: &App) -> f64 {
+    app.world()
+        .resource::<DiagnosticsStore>()
+        .get(&FixedViewportPlugin::SYNCS)
+        .and_then(|diagnostic| diagnostic.value())
+        .expect("the sync should have been measured")
+}
+
+/// Asserts the camera's viewport has the given physical position and size.
+fn assert_viewport(app: &App, camera: Entity, position: UVec2, size: UVec2) {
+    let viewport = viewport(app, camera).expect("the camera should have a viewport");
+    assert_eq!(viewport.physical_position, position);
+    assert_eq!(viewport.physical_size, size);
+}
+
+#[test]
+fn letterboxes_tall_windows() {
+    let (app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn pillarboxes_wide_windows() {
+    let (app, _, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(4. / 3.));
+    assert_viewport(&app, camera, UVec2::new(240, 0), UVec2::new(1440, 1080));
+}
+
+#[test]
+fn covers_matching_windows() {
+    let (app, _, camera) = setup(UVec2::new(1280, 720), FixedViewport::new(16. / 9.));
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1280, 720));
+}
+
+#[test]
+fn refits_when_the_window_is_resized() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+}
+
+#[test]
+fn syncs_once_for_a_burst_of_resize_events() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+
+    // a window dragged quickly can report many sizes in one frame, which only need the last one
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    for width in 1031..1081 {
+        app.world_mut().send_event(WindowResized {
+            window,
+            width: width as f32,
+            height: 1080.,
+        });
+    }
+    app.update();
+
+    assert_eq!(syncs(&app), 1.);
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+}
+
+#[test]
+fn keeps_the_last_viewport_while_minimized() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1920, 0);
+    app.world_mut().send_event(WindowResized {
+        window,
+        width: 1920.,
+        height: 0.,
+    });
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn never_shrinks_the_viewport_to_nothing() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(4. / 3.));
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1, 1);
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::ONE);
+}
+
+#[test]
+fn syncs_once_for_a_resize_and_scale_factor_change_in_one_frame() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.world_mut().send_event(WindowResized {
+        window,
+        width: 1080.,
+        height: 1080.,
+    });
+    app.world_mut().send_event(WindowScaleFactorChanged {
+        window,
+        scale_factor: 1.,
+    });
+    app.update();
+
+    assert_eq!(syncs(&app), 1.);
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+}
+
+#[test]
+fn keeps_the_last_viewport_for_an_invalid_aspect_ratio() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .aspect_ratio = AspectRatio::Value(-1.);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn refits_in_the_same_frame_as_a_scale_factor_override() {
+    let fixed_viewport = FixedViewport {
+        use_logical_size: true,
+        ..FixedViewport::fixed_resolution(320, 180)
+    };
+    let (mut app, window, camera) = setup(UVec2::new(1600, 900), fixed_viewport);
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1600, 900));
+
+    // the logical window halves, so the base resolution only fits twice over
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_scale_factor_override(Some(2.));
+    app.world_mut().send_event(WindowScaleFactorChanged {
+        window,
+        scale_factor: 2.,
+    });
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(160, 90), UVec2::new(1280, 720));
+}
+
+#[test]
+fn refits_when_the_fixed_viewport_changes() {
+    // an exact aspect ratio is replaced by a float one like any other
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), FixedViewport::from_ratio(16, 9));
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .aspect_ratio = AspectRatio::Value(1.);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(420, 0), UVec2::new(1080, 1080));
+}
+
+#[test]
+fn refits_in_the_same_update_when_only_the_mode_changes() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(4. / 3.));
+    assert_viewport(&app, camera, UVec2::new(240, 0), UVec2::new(1440, 1080));
+
+    // no window event is sent, the change to the component alone triggers the sync
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .mode = ScaleMode::Stretch;
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+}
+
+#[test]
+fn resets_the_viewport_when_removed() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    app.world_mut().entity_mut(camera).remove::<FixedViewport>();
+    app.update();
+    assert!(viewport(&app, camera).is_none());
+}
+
+#[test]
+fn fits_cameras_sharing_a_window_independently() {
+    let (mut app, _, main) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    // a square minimap drawn over the top right corner of the window
+    let minimap = app
+        .world_mut()
+        .spawn((
+            Camera {
+                order: 1,
+                ..default()
+            },
+            FixedViewport {
+                region: Rect::new(0.75, 0., 1., 0.25),
+                ..FixedViewport::new(1.)
+            },
+        ))
+        .id();
+    app.update();
+    assert_viewport(&app, main, UVec2::ZERO, UVec2::new(1920, 1080));
+    assert_viewport(&app, minimap, UVec2::new(1545, 0), UVec2::new(270, 270));
+}
+
+#[test]
+fn reports_the_scale_of_the_base_resolution() {
+    let (app, _, camera) = setup(
+        UVec2::new(1920, 1200),
+        FixedViewport::fixed_resolution(320, 180),
+    );
+    assert_eq!(
+        app.world().get::<ViewportScale>(camera),
+        Some(&ViewportScale(6.))
+    );
+}
+
+#[test]
+fn rounds_down_to_the_grid() {
+    let fixed_viewport = FixedViewport {
+        grid_align: Some(16),
+        ..FixedViewport::new(16. / 9.)
+    };
+    let viewport = compute_fixed_viewport(UVec2::new(1000, 1000), &fixed_viewport).unwrap();
+    assert_eq!(viewport.physical_size, UVec2::new(992, 560));
+    assert_eq!(viewport.physical_position, UVec2::new(4, 220));
+}
+
+#[test]
+fn syncs_immediately_on_demand() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1920, 1200);
+    sync_camera_viewport(app.world_mut(), camera);
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn syncs_only_the_new_requests_on_demand() {
+    let (mut app, _, first) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    let second = app
+        .world_mut()
+        .spawn((Camera::default(), FixedViewport::new(4. / 3.)))
+        .id();
+    sync_camera_viewport(app.world_mut(), first);
+    assert_eq!(syncs(&app), 1.);
+
+    // the first request was already handled, so it isn't synced again
+    sync_camera_viewport(app.world_mut(), second);
+    assert_eq!(syncs(&app), 1.);
+    assert_viewport(&app, second, UVec2::new(240, 0), UVec2::new(1440, 1080));
+}
+
+#[test]
+fn registers_synced_viewports() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    let registry = app.world().resource::<FixedViewportRegistry>();
+    assert_eq!(
+        registry.get(camera).map(|viewport| viewport.physical_size),
+        Some(UVec2::new(1920, 1080))
+    );
+
+    app.world_mut().despawn(camera);
+    app.update();
+    assert!(app.world().resource::<FixedViewportRegistry>().is_empty());
+}
+
+#[test]
+fn falls_back_to_the_only_window_without_a_primary_window() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default());
+    spawn_test_window(&mut app, UVec2::new(1920, 1200), false);
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), FixedViewport::new(16. / 9.)))
+        .id();
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn falls_back_to_the_lowest_entity_with_multiple_primary_windows() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default());
+    let tall = spawn_test_window(&mut app, UVec2::new(1920, 1200), true);
+    let square = spawn_test_window(&mut app, UVec2::new(1080, 1080), true);
+    assert!(tall < square);
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), FixedViewport::new(16. / 9.)))
+        .id();
+    assert_eq!(app.world().resource::<PrimaryWindowFallback>().0, None);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+    // the fallback was warned about
+    assert_eq!(
+        app.world().resource::<PrimaryWindowFallback>().0,
+        Some(tall)
+    );
+
+    // the choice is stable from one sync to the next
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn syncs_cameras_spawned_before_their_window() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default());
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), FixedViewport::new(16. / 9.)))
+        .id();
+    app.update();
+    assert!(viewport(&app, camera).is_none());
+
+    spawn_test_window(&mut app, UVec2::new(1920, 1200), true);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn refits_when_the_camera_moves_to_another_window() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    let square = spawn_test_window(&mut app, UVec2::new(1080, 1080), false);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+
+    app.world_mut().get_mut::<Camera>(camera).unwrap().target =
+        RenderTarget::Window(WindowRef::Entity(square));
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+
+    app.world_mut().get_mut::<Camera>(camera).unwrap().target =
+        RenderTarget::Window(WindowRef::Primary);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn syncs_when_moved_from_an_unresolvable_target_to_a_window() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    let square = spawn_test_window(&mut app, UVec2::new(1080, 1080), false);
+    let missing = app.world_mut().spawn_empty().id();
+    app.world_mut().despawn(missing);
+    // a camera spawned for a window that doesn't exist is never synced
+    let lost = app
+        .world_mut()
+        .spawn((
+            Camera {
+                target: RenderTarget::Window(WindowRef::Entity(missing)),
+                ..default()
+            },
+            FixedViewport::new(16. / 9.),
+        ))
+        .id();
+    app.update();
+    assert!(viewport(&app, lost).is_none());
+
+    app.world_mut().get_mut::<Camera>(lost).unwrap().target =
+        RenderTarget::Window(WindowRef::Entity(square));
+    app.update();
+    assert_viewport(&app, lost, UVec2::new(0, 236), UVec2::new(1080, 608));
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn resets_the_viewport_when_the_target_window_is_despawned() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    let square = spawn_test_window(&mut app, UVec2::new(1080, 1080), false);
+    app.world_mut().get_mut::<Camera>(camera).unwrap().target =
+        RenderTarget::Window(WindowRef::Entity(square));
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+
+    app.world_mut().despawn(square);
+    app.update();
+    assert!(viewport(&app, camera).is_none());
+    assert!(app.world().get::<CurrentViewport>(camera).is_none());
+}
+
+#[test]
+fn refits_when_the_base_resolution_changes() {
+    let (mut app, _, camera) = setup(
+        UVec2::new(1600, 900),
+        FixedViewport::fixed_resolution(320, 180),
+    );
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1600, 900));
+
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .mode = ScaleMode::Integer {
+        base_resolution: UVec2::new(480, 270),
+    };
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(80, 45), UVec2::new(1440, 810));
+    assert_eq!(
+        app.world().get::<ViewportScale>(camera),
+        Some(&ViewportScale(3.))
+    );
+}
+
+#[test]
+fn fills_nearly_matching_windows_within_the_tolerance() {
+    let fixed_viewport = FixedViewport {
+        aspect_tolerance: 0.01,
+        ..FixedViewport::new(16. / 9.)
+    };
+    let viewport = compute_fixed_viewport(UVec2::new(1921, 1080), &fixed_viewport).unwrap();
+    assert_eq!(viewport.physical_position, UVec2::ZERO);
+    assert_eq!(viewport.physical_size, UVec2::new(1921, 1080));
+}
+
+#[test]
+fn syncs_grouped_cameras_to_the_same_viewport() {
+    let (mut app, window, background) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .entity_mut(background)
+        .insert(FixedViewportGroup(0));
+    // the other cameras are ordered above the background and asked for a different ratio, which the group overrides
+    let cameras: Vec<Entity> = (1..3)
+        .map(|order| {
+            app.world_mut()
+                .spawn((
+                    Camera { order, ..default() },
+                    FixedViewport::new(4. / 3.),
+                    FixedViewportGroup(0),
+                ))
+                .id()
+        })
+        .collect();
+    app.update();
+
+    // a single update after the resize moves every camera in the group together
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.update();
+    for camera in [background].into_iter().chain(cameras) {
+        assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+    }
+}
+
+#[test]
+fn fits_grouped_cameras_per_target_size_and_ignores_disabled_ones() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(FixedViewportGroup(0));
+    // a disabled camera ordered first and a camera on a smaller window share the group
+    app.world_mut().spawn((
+        Camera {
+            order: -1,
+            ..default()
+        },
+        FixedViewport {
+            enabled: false,
+            ..FixedViewport::new(16. / 9.)
+        },
+        FixedViewportGroup(0),
+    ));
+    let small_window = spawn_test_window(&mut app, UVec2::new(400, 300), false);
+    let small_camera = app
+        .world_mut()
+        .spawn((
+            Camera {
+                order: 1,
+                target: RenderTarget::Window(WindowRef::Entity(small_window)),
+                ..default()
+            },
+            FixedViewport::new(16. / 9.),
+            FixedViewportGroup(0),
+        ))
+        .id();
+    app.update();
+
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+    let small_viewport =
+        compute_fixed_viewport(UVec2::new(400, 300), &FixedViewport::new(16. / 9.)).unwrap();
+    assert_viewport(
+        &app,
+        small_camera,
+        small_viewport.physical_position,
+        small_viewport.physical_size,
+    );
+}
+
+#[test]
+fn locks_the_projection_to_the_base_resolution() {
+    let fixed_viewport = FixedViewport {
+        projection: ProjectionLock::BaseResolution,
+        ..FixedViewport::fixed_resolution(320, 180)
+    };
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), fixed_viewport);
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(OrthographicProjection::default());
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    let projection = app.world().get::<OrthographicProjection>(camera).unwrap();
+    assert!(matches!(
+        projection.scaling_mode,
+        ScalingMode::Fixed {
+            width: 320.,
+            height: 180.
+        }
+    ));
+}
+
+#[test]
+fn crops_base_resolutions_larger_than_the_window() {
+    let fixed_viewport = FixedViewport {
+        projection: ProjectionLock::BaseResolution,
+        ..FixedViewport::fixed_resolution(1280, 720)
+    };
+    let (mut app, _, camera) = setup(UVec2::new(1000, 1000), fixed_viewport);
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(OrthographicProjection::default());
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    // drawn at 1x and centered, with the sides that don't fit cut off rather than squeezed in
+    assert_viewport(&app, camera, UVec2::new(0, 140), UVec2::new(1000, 720));
+    assert_eq!(
+        visible_area(&app, camera),
+        Rect::new(-500., -360., 500., 360.)
+    );
+}
+
+#[test]
+fn leaves_the_camera_and_projection_unchanged_when_the_sync_matches() {
+    let fixed_viewport = FixedViewport {
+        projection: ProjectionLock::BaseResolution,
+        ..FixedViewport::fixed_resolution(320, 180)
+    };
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), fixed_viewport);
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(OrthographicProjection::default());
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    let last_changed = |app: &App| {
+        let entity = app.world().entity(camera);
+        (
+            entity.get_ref::<Camera>().unwrap().last_changed(),
+            entity
+                .get_ref::<OrthographicProjection>()
+                .unwrap()
+                .last_changed(),
+        )
+    };
+    let before = last_changed(&app);
+
+    // a sync yielding the same viewport and scaling mode writes neither
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    assert_eq!(last_changed(&app), before);
+}
+
+#[test]
+fn leaves_the_rest_of_the_camera_alone() {
+    // bevy 0.14 has no sub camera view to preserve, so check every other field a crop could live in
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(4. / 3.));
+    let mut camera_mut = app.world_mut().get_mut::<Camera>(camera).unwrap();
+    camera_mut.order = 3;
+    camera_mut.hdr = true;
+    camera_mut.msaa_writeback = false;
+    camera_mut.clear_color = ClearColorConfig::Custom(Color::WHITE);
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.update();
+
+    assert_viewport(&app, camera, UVec2::new(0, 135), UVec2::new(1080, 810));
+    let camera = app.world().get::<Camera>(camera).unwrap();
+    assert_eq!(camera.order, 3);
+    assert!(camera.hdr);
+    assert!(!camera.msaa_writeback);
+    assert!(matches!(
+        camera.clear_color,
+        ClearColorConfig::Custom(color) if color == Color::WHITE
+    ));
+}
+
+#[test]
+fn leaves_the_projection_alone_without_a_lock() {
+    let (mut app, _, camera) = setup(
+        UVec2::new(1920, 1080),
+        FixedViewport::fixed_resolution(320, 180),
+    );
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(OrthographicProjection::default());
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    let projection = app.world().get::<OrthographicProjection>(camera).unwrap();
+    assert!(matches!(
+        projection.scaling_mode,
+        ScalingMode::WindowSize(_)
+    ));
+}
+
+#[test]
+fn syncs_in_the_given_schedule() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::in_schedule(Update));
+    spawn_test_window(&mut app, UVec2::new(1920, 1200), true);
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), FixedViewport::new(16. / 9.)))
+        .id();
+    app.world_mut().run_schedule(Update);
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn applies_the_viewport_adjuster() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    // push the viewport to the top, then try to push it past the bottom of the window
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(ViewportAdjuster::new(|mut viewport, _| {
+            viewport.physical_position.y = 0;
+            viewport
+        }));
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(ViewportAdjuster::new(|mut viewport, target_size| {
+            viewport.physical_position.y = target_size.y;
+            viewport
+        }));
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 120), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn refits_when_the_viewport_adjuster_is_removed() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(ViewportAdjuster::new(|mut viewport, _| {
+            viewport.physical_position.y = 0;
+            viewport
+        }));
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+
+    app.world_mut()
+        .entity_mut(camera)
+        .remove::<ViewportAdjuster>();
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+/// A group led by a camera whose adjuster pushes its viewport to the top of the window,
+/// which the follower takes as is.
+fn setup_adjusted_group() -> (App, Entity, Entity) {
+    let (mut app, _, leader) = setup(UVec2::new(1920, 1200), FixedViewport::new(16. / 9.));
+    app.world_mut().entity_mut(leader).insert((
+        FixedViewportGroup(0),
+        ViewportAdjuster::new(|mut viewport, _| {
+            viewport.physical_position.y = 0;
+            viewport
+        }),
+    ));
+    let follower = app
+        .world_mut()
+        .spawn((
+            Camera {
+                order: 1,
+                ..default()
+            },
+            FixedViewport::new(16. / 9.),
+            FixedViewportGroup(0),
+        ))
+        .id();
+    app.update();
+    assert_viewport(&app, follower, UVec2::ZERO, UVec2::new(1920, 1080));
+    (app, leader, follower)
+}
+
+#[test]
+fn refits_the_group_when_a_camera_leaves_it() {
+    let (mut app, leader, follower) = setup_adjusted_group();
+    app.world_mut()
+        .entity_mut(leader)
+        .remove::<FixedViewportGroup>();
+    app.update();
+    assert_viewport(&app, leader, UVec2::ZERO, UVec2::new(1920, 1080));
+    assert_viewport(&app, follower, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn refits_the_group_when_a_camera_moves_to_another() {
+    let (mut app, leader, follower) = setup_adjusted_group();
+    app.world_mut()
+        .get_mut::<FixedViewportGroup>(leader)
+        .unwrap()
+        .0 = 1;
+    app.update();
+    assert_viewport(&app, leader, UVec2::ZERO, UVec2::new(1920, 1080));
+    assert_viewport(&app, follower, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn refits_the_group_when_a_camera_loses_its_fixed_viewport() {
+    let (mut app, leader, follower) = setup_adjusted_group();
+    app.world_mut().entity_mut(leader).remove::<FixedViewport>();
+    app.update();
+    assert!(viewport(&app, leader).is_none());
+    assert_viewport(&app, follower, UVec2::new(0, 60), UVec2::new(1920, 1080));
+}
+
+#[test]
+fn leaves_manual_cameras_in_a_group_alone_on_resize() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(FixedViewportGroup(0));
+    let manual = app
+        .world_mut()
+        .spawn((
+            Camera {
+                order: 1,
+                ..default()
+            },
+            FixedViewport {
+                manual: true,
+                ..FixedViewport::new(16. / 9.)
+            },
+            FixedViewportGroup(0),
+        ))
+        .id();
+    app.update();
+    assert_viewport(&app, manual, UVec2::ZERO, UVec2::new(1920, 1080));
+
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+    assert_viewport(&app, manual, UVec2::ZERO, UVec2::new(1920, 1080));
+}
+
+#[test]
+fn fits_to_the_canvas_without_applying_it() {
+    let fixed_viewport = FixedViewport {
+        canvas_size: Some(UVec2::new(4000, 3000)),
+        ..FixedViewport::new(16. / 9.)
+    };
+    let (app, _, camera) = setup(UVec2::new(1920, 1080), fixed_viewport);
+    assert!(viewport(&app, camera).is_none());
+    let current_viewport = &app.world().get::<CurrentViewport>(camera).unwrap().0;
+    assert_eq!(current_viewport.physical_position, UVec2::new(0, 375));
+    assert_eq!(current_viewport.physical_size, UVec2::new(4000, 2250));
+}
+
+#[test]
+fn skips_inactive_cameras_until_they_are_active_again() {
+    let (mut app, window, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.world_mut().get_mut::<Camera>(camera).unwrap().is_active = false;
+    app.update();
+
+    // the resize is ignored while inactive
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.update();
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+
+    app.world_mut().get_mut::<Camera>(camera).unwrap().is_active = true;
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 236), UVec2::new(1080, 608));
+}
+
+#[test]
+fn refits_to_the_whole_window_when_the_available_rect_is_removed() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.insert_resource(AvailableViewportRect(Rect::new(0., 0., 960., 1080.)));
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(0, 270), UVec2::new(960, 540));
+
+    app.world_mut().remove_resource::<AvailableViewportRect>();
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+}
+
+#[test]
+fn refits_to_the_whole_window_when_the_safe_area_insets_are_removed() {
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), FixedViewport::new(16. / 9.));
+    app.insert_resource(SafeAreaInsets {
+        left: 960,
+        ..default()
+    });
+    app.update();
+    assert_viewport(&app, camera, UVec2::new(960, 270), UVec2::new(960, 540));
+
+    app.world_mut().remove_resource::<SafeAreaInsets>();
+    app.update();
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+}
+
+#[test]
+fn crops_the_projection_of_filled_viewports() {
+    let fixed_viewport = FixedViewport {
+        mode: ScaleMode::Fill,
+        ..FixedViewport::new(1.)
+    };
+    let (mut app, _, camera) = setup(UVec2::new(1920, 1080), fixed_viewport);
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(OrthographicProjection {
+            scaling_mode: ScalingMode::AutoMin {
+                min_width: 100.,
+                min_height: 100.,
+            },
+            ..default()
+        });
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    // the square fit covers the window, so its top and bottom are cut off evenly
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1920, 1080));
+    assert_eq!(
+        visible_area(&app, camera),
+        Rect::new(-50., -28.125, 50., 28.125)
+    );
+
+    // aligning to the top cuts off only the bottom
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .alignment = ViewportAlignment::Top;
+    app.update();
+    assert_eq!(visible_area(&app, camera), Rect::new(-50., -6.25, 50., 50.));
+
+    // fitting again puts the projection back the way it was
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .mode = ScaleMode::Fit;
+    app.update();
+    let projection = app.world().get::<OrthographicProjection>(camera).unwrap();
+    assert!(matches!(
+        projection.scaling_mode,
+        ScalingMode::AutoMin {
+            min_width: 100.,
+            min_height: 100.
+        }
+    ));
+    assert_eq!(projection.viewport_origin, Vec2::splat(0.5));
+    assert_eq!(visible_area(&app, camera), Rect::new(-50., -50., 50., 50.));
+}
+
+#[test]
+fn crops_the_projection_instead_of_leaving_disallowed_bars() {
+    let fixed_viewport = FixedViewport {
+        allow_horizontal_bars: false,
+        ..FixedViewport::new(1.)
+    };
+    let (mut app, _, camera) = setup(UVec2::new(1080, 1920), fixed_viewport);
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(OrthographicProjection {
+            scaling_mode: ScalingMode::AutoMin {
+                min_width: 100.,
+                min_height: 100.,
+            },
+            ..default()
+        });
+    app.world_mut()
+        .get_mut::<FixedViewport>(camera)
+        .unwrap()
+        .set_changed();
+    app.update();
+    // the square covers the tall window rather than leaving bars above and below it, cutting off its sides
+    assert_viewport(&app, camera, UVec2::ZERO, UVec2::new(1080, 1920));
+    assert_eq!(
+        visible_area(&app, camera),
+        Rect::new(-28.125, -50., 28.125, 50.)
+    );
+}
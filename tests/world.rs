@@ -0,0 +1,58 @@
+use bevy::{
+    prelude::*,
+    render::camera::{camera_system, ManualTextureViews},
+};
+use bevy_fixed_viewport::{
+    testing::spawn_test_window, FixedViewport, FixedViewportPlugin, FixedViewportSet,
+};
+
+/// An app with the plugin, a fake primary window and a 2D camera rendering to it from the given transform.
+fn setup(camera_transform: GlobalTransform) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(FixedViewportPlugin::default())
+        .init_resource::<Assets<Image>>()
+        .init_resource::<ManualTextureViews>()
+        .add_systems(
+            PostUpdate,
+            camera_system::<OrthographicProjection>.after(FixedViewportSet::Sync),
+        );
+    spawn_test_window(&mut app, UVec2::new(1920, 1200), true);
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera::default(),
+            OrthographicProjection::default(),
+            camera_transform,
+            FixedViewport::new(16. / 9.),
+        ))
+        .id();
+    app.update();
+    (app, camera)
+}
+
+/// The world rect visible through the camera.
+fn visible_world_rect(app: &App, camera: Entity) -> Rect {
+    let camera = app.world().entity(camera);
+    FixedViewport::visible_world_rect(
+        camera.get::<Camera>().unwrap(),
+        camera.get::<GlobalTransform>().unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn visible_world_rect_covers_only_the_viewport() {
+    let (app, camera) = setup(GlobalTransform::IDENTITY);
+    let rect = visible_world_rect(&app, camera);
+    assert!(rect.min.abs_diff_eq(Vec2::new(-960., -540.), 0.01));
+    assert!(rect.max.abs_diff_eq(Vec2::new(960., 540.), 0.01));
+}
+
+#[test]
+fn visible_world_rect_follows_the_global_transform() {
+    let (app, camera) = setup(GlobalTransform::from_xyz(100., 50., 0.));
+    let rect = visible_world_rect(&app, camera);
+    assert!(rect.min.abs_diff_eq(Vec2::new(-860., -490.), 0.01));
+    assert!(rect.max.abs_diff_eq(Vec2::new(1060., 590.), 0.01));
+}